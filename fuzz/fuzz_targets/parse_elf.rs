@@ -0,0 +1,12 @@
+//! Parse arbitrary bytes and decode everything that parsed, through
+//! `mark_readelf::fuzz::exercise`. Any input may be rejected with an error, but none
+//! may panic.
+//!
+//! Run with `cargo +nightly fuzz run parse_elf`. Inputs that once crashed belong in
+//! `corpus/parse_elf`, which `tests/fuzz_corpus.rs` replays on every `cargo test`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| mark_readelf::fuzz::exercise(data));
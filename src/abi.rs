@@ -98,7 +98,7 @@ pub const ELFOSABI_FENIXOS: u8 = 16;
 pub const ELFOSABI_CLOUDABI: u8 = 17;
 /// Stratus Technologies OpenVOS
 pub const ELFOSABI_OPENVOS: u8 = 18;
-/// 64-255 Architecture-specific value range
+// 64-255 Architecture-specific value range
 
 // ET_* define constants for the ELF File Header's e_type field.
 // Represented as Elf32_Half in Elf32_Ehdr and Elf64_Half in Elf64_Ehdr which
@@ -563,6 +563,16 @@ pub const PT_GNU_STACK: u32 = 0x6474e551;
 pub const PT_GNU_RELRO: u32 = 0x6474e552;
 /// The segment contains .note.gnu.property section
 pub const PT_GNU_PROPERTY: u32 = 0x6474e553;
+/// SFrame stack trace information
+pub const PT_GNU_SFRAME: u32 = 0x6474e554;
+/// Number of memory binding types, each with its own PT_GNU_MBIND_* value
+pub const PT_GNU_MBIND_NUM: u32 = 4096;
+/// Values between [PT_GNU_MBIND_LO, PT_GNU_MBIND_HI] in this inclusive range mark
+/// memory to be bound to a particular type of memory.
+pub const PT_GNU_MBIND_LO: u32 = 0x6474e555;
+/// Values between [PT_GNU_MBIND_LO, PT_GNU_MBIND_HI] in this inclusive range mark
+/// memory to be bound to a particular type of memory.
+pub const PT_GNU_MBIND_HI: u32 = PT_GNU_MBIND_LO + PT_GNU_MBIND_NUM - 1;
 /// Values between [PT_LOOS, PT_HIOS] in this inclusive range are reserved for
 /// operating system-specific semantics.
 pub const PT_LOOS: u32 = 0x60000000;
@@ -727,6 +737,9 @@ pub const SHF_COMPRESSED: u32 = 1 << 11;
 pub const SHF_MASKOS: u32 = 0x0ff00000;
 /// Masked bits are reserved for processor-specific semantics.
 pub const SHF_MASKPROC: u32 = 0xf0000000;
+/// Section is left out of the link unless something refers to it. A GNU extension in
+/// the processor-specific range, which readelf shows as E.
+pub const SHF_EXCLUDE: u32 = 0x80000000;
 
 // STT_* define constants for the ELF Symbol's st_type (encoded in the st_info field).
 
@@ -1120,6 +1133,8 @@ pub const VER_NDX_GLOBAL: u16 = 1;
 pub const VER_NDX_VERSION: u16 = 0x7fff;
 /// Symbol is hidden
 pub const VER_NDX_HIDDEN: u16 = 0x8000;
+/// A hidden VER_NDX_GLOBAL, which readelf doesn't look up among the version definitions
+pub const VER_NDX_HIDDEN_GLOBAL: u16 = VER_NDX_HIDDEN | VER_NDX_GLOBAL;
 
 // .gnu.version_d VerDef.vd_version reserved values
 /// Only defined valid vd_version value
@@ -1279,6 +1294,21 @@ pub const ELF_NOTE_GNU_ABI_TAG_OS_GNU: u32 = 1;
 pub const ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2: u32 = 2;
 pub const ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD: u32 = 3;
 
+// Property types in an NT_GNU_PROPERTY_TYPE_0 note. Each property is a pr_type word, a
+// pr_datasz word and pr_datasz bytes of data, padded to 4 bytes in ELF32 and 8 in ELF64.
+/// Stack size, as an address-sized value
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+/// No copy relocation on protected data symbol
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+/// Start of the processor-specific property range
+pub const GNU_PROPERTY_LOPROC: u32 = 0xc0000000;
+/// End of the processor-specific property range
+pub const GNU_PROPERTY_HIPROC: u32 = 0xdfffffff;
+/// Start of the application-specific property range
+pub const GNU_PROPERTY_LOUSER: u32 = 0xe0000000;
+/// End of the application-specific property range
+pub const GNU_PROPERTY_HIUSER: u32 = 0xffffffff;
+
 //     _    ____  __  __
 //    / \  |  _ \|  \/  |
 //   / _ \ | |_) | |\/| |
@@ -1289,6 +1319,10 @@ pub const ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD: u32 = 3;
 // See: https://github.com/ARM-software/abi-aa/blob/main/aaelf32/aaelf32.rst
 // See: https://github.com/ARM-software/abi-aa/blob/main/aaelf64/aaelf64.rst
 
+/// Legacy flag for a relocatable executable, meaningful under any EABI version
+pub const EF_ARM_RELEXEC: u32 = 0x01;
+/// Legacy flag for position independent code, meaningful under any EABI version
+pub const EF_ARM_PIC: u32 = 0x20;
 /// Set in executable file headers (e_type = ET_EXEC or ET_DYN) to note explicitly that the
 /// executable file was built to conform to the software floating-point procedure-call standard
 /// (the base standard). If both EF_ARM_ABI_FLOAT_XXXX bits are clear, conformance to the base
@@ -1305,6 +1339,8 @@ pub const EF_ARM_VFP_FLOAT: u32 = EF_ARM_ABI_FLOAT_HARD;
 /// The ELF file contains BE-8 code, suitable for execution on an Arm Architecture v6 processor.
 /// This flag must only be set on an executable file.
 pub const EF_ARM_BE8: u32 = 0x00800000;
+/// The ELF file contains LE-8 code. Only seen in legacy (ABI version 4) files.
+pub const EF_ARM_LE8: u32 = 0x00400000;
 
 /// Legacy code (ABI version 4 and earlier) generated by gcc-arm-xxx might use these bits.
 pub const EF_ARM_GCCMASK: u32 = 0x00400FFF;
@@ -2316,7 +2352,7 @@ pub const R_PPC64_DTPMOD64: u32 = 68;
 /// `@tprel`
 pub const R_PPC64_TPREL16: u32 = 69;
 /// `#lo(@tprel)`
-pub const R_PPC64_TPREL16_LO: u32 = 60;
+pub const R_PPC64_TPREL16_LO: u32 = 70;
 /// `#hi(@tprel)`
 pub const R_PPC64_TPREL16_HI: u32 = 71;
 /// `#ha(@tprel)`
@@ -2561,6 +2597,92 @@ pub const R_RISCV_32_PCREL: u32 = 57;
 /// Relocation against a non-preemptible ifunc symbolifunc_resolver: `(B + A)`
 pub const R_RISCV_IRELATIVE: u32 = 58;
 
+//  _ _____  ___   __
+// (_)___ / ( _ ) / /_
+// | | |_ \ / _ \| '_ \
+// | |___) | (_) | (_) |
+// |_|____/ \___/ \___/
+//
+// See: https://gitlab.com/x86-psABIs/i386-ABI
+
+// i386 reloc types
+//
+// A Represents the addend used to compute the value of the relocatable field.
+//     i386 uses SHT_REL, so the addend is the value already stored at the place.
+// B Represents the base address at which a shared object has been loaded into memory
+//     during execution.
+// G Represents the offset into the global offset table at which the relocation entry's symbol
+//     will reside during execution.
+// GOT Represents the address of the global offset table.
+// L Represents the place (section offset or address) of the Procedure Linkage Table entry for a symbol.
+// P Represents the place (section offset or address) of the storage unit being relocated (computed using r_offset).
+// S Represents the value of the symbol whose index resides in the relocation entry.
+// Z Represents the size of the symbol whose index resides in the relocation entry.
+
+pub const R_386_NONE: u32 = 0;
+/// `S + A`
+pub const R_386_32: u32 = 1;
+/// `S + A - P`
+pub const R_386_PC32: u32 = 2;
+/// `G + A`
+pub const R_386_GOT32: u32 = 3;
+/// `L + A - P`
+pub const R_386_PLT32: u32 = 4;
+pub const R_386_COPY: u32 = 5;
+/// `S`
+pub const R_386_GLOB_DAT: u32 = 6;
+/// `S`
+pub const R_386_JUMP_SLOT: u32 = 7;
+/// `B + A`
+pub const R_386_RELATIVE: u32 = 8;
+/// `S + A - GOT`
+pub const R_386_GOTOFF: u32 = 9;
+/// `GOT + A - P`
+pub const R_386_GOTPC: u32 = 10;
+/// `L + A`
+pub const R_386_32PLT: u32 = 11;
+pub const R_386_TLS_TPOFF: u32 = 14;
+pub const R_386_TLS_IE: u32 = 15;
+pub const R_386_TLS_GOTIE: u32 = 16;
+pub const R_386_TLS_LE: u32 = 17;
+pub const R_386_TLS_GD: u32 = 18;
+pub const R_386_TLS_LDM: u32 = 19;
+/// `S + A`
+pub const R_386_16: u32 = 20;
+/// `S + A - P`
+pub const R_386_PC16: u32 = 21;
+/// `S + A`
+pub const R_386_8: u32 = 22;
+/// `S + A - P`
+pub const R_386_PC8: u32 = 23;
+pub const R_386_TLS_GD_32: u32 = 24;
+pub const R_386_TLS_GD_PUSH: u32 = 25;
+pub const R_386_TLS_GD_CALL: u32 = 26;
+pub const R_386_TLS_GD_POP: u32 = 27;
+pub const R_386_TLS_LDM_32: u32 = 28;
+pub const R_386_TLS_LDM_PUSH: u32 = 29;
+pub const R_386_TLS_LDM_CALL: u32 = 30;
+pub const R_386_TLS_LDM_POP: u32 = 31;
+pub const R_386_TLS_LDO_32: u32 = 32;
+pub const R_386_TLS_IE_32: u32 = 33;
+pub const R_386_TLS_LE_32: u32 = 34;
+pub const R_386_TLS_DTPMOD32: u32 = 35;
+pub const R_386_TLS_DTPOFF32: u32 = 36;
+pub const R_386_TLS_TPOFF32: u32 = 37;
+/// `Z + A`
+pub const R_386_SIZE32: u32 = 38;
+pub const R_386_TLS_GOTDESC: u32 = 39;
+pub const R_386_TLS_DESC_CALL: u32 = 40;
+pub const R_386_TLS_DESC: u32 = 41;
+/// `indirect (B + A)`
+pub const R_386_IRELATIVE: u32 = 42;
+/// `G + A`
+pub const R_386_GOT32X: u32 = 43;
+/// GNU C++ vtable garbage collection hierarchy
+pub const R_386_GNU_VTINHERIT: u32 = 250;
+/// GNU C++ vtable garbage collection member usage
+pub const R_386_GNU_VTENTRY: u32 = 251;
+
 //       ___   __      __   _  _
 // __  _( _ ) / /_    / /_ | || |
 // \ \/ / _ \| '_ \  | '_ \| || |_
@@ -2577,6 +2699,46 @@ pub const SHF_X86_64_LARGE: u64 = 0x10000000;
 /// This section contains unwind function table entries for stack unwinding.
 pub const SHT_X86_64_UNWIND: u32 = 0x70000001; // SHT_LOPROC + 1;
 
+// GNU property types for x86, shared by x86_64 and i386. Each holds a 4-byte bitmask.
+/// Features every object in the link supports, like CET
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+/// Hardware features the object needs
+pub const GNU_PROPERTY_X86_FEATURE_2_NEEDED: u32 = 0xc0008001;
+/// ISA level the object needs
+pub const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xc0008002;
+/// Hardware features the object uses
+pub const GNU_PROPERTY_X86_FEATURE_2_USED: u32 = 0xc0010001;
+/// ISA level the object uses
+pub const GNU_PROPERTY_X86_ISA_1_USED: u32 = 0xc0010002;
+
+/// Compatible with Indirect Branch Tracking
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 1 << 0;
+/// Compatible with Shadow Stack
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 1 << 1;
+/// Compatible with Linear Address Masking for 48-bit user addresses
+pub const GNU_PROPERTY_X86_FEATURE_1_LAM_U48: u32 = 1 << 2;
+/// Compatible with Linear Address Masking for 57-bit user addresses
+pub const GNU_PROPERTY_X86_FEATURE_1_LAM_U57: u32 = 1 << 3;
+
+pub const GNU_PROPERTY_X86_FEATURE_2_X86: u32 = 1 << 0;
+pub const GNU_PROPERTY_X86_FEATURE_2_X87: u32 = 1 << 1;
+pub const GNU_PROPERTY_X86_FEATURE_2_MMX: u32 = 1 << 2;
+pub const GNU_PROPERTY_X86_FEATURE_2_XMM: u32 = 1 << 3;
+pub const GNU_PROPERTY_X86_FEATURE_2_YMM: u32 = 1 << 4;
+pub const GNU_PROPERTY_X86_FEATURE_2_ZMM: u32 = 1 << 5;
+pub const GNU_PROPERTY_X86_FEATURE_2_FXSR: u32 = 1 << 6;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVE: u32 = 1 << 7;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVEOPT: u32 = 1 << 8;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVEC: u32 = 1 << 9;
+pub const GNU_PROPERTY_X86_FEATURE_2_TMM: u32 = 1 << 10;
+pub const GNU_PROPERTY_X86_FEATURE_2_MASK: u32 = 1 << 11;
+
+/// The x86-64 microarchitecture levels, see the psABI
+pub const GNU_PROPERTY_X86_ISA_1_BASELINE: u32 = 1 << 0;
+pub const GNU_PROPERTY_X86_ISA_1_V2: u32 = 1 << 1;
+pub const GNU_PROPERTY_X86_ISA_1_V3: u32 = 1 << 2;
+pub const GNU_PROPERTY_X86_ISA_1_V4: u32 = 1 << 3;
+
 // x86_64 reloc types
 //
 // A Represents the addend used to compute the value of the relocatable field.
@@ -0,0 +1,390 @@
+//! Command line handling. Mirrors the GNU readelf option syntax, so the
+//! short selectors can be bundled (`-hl`) and each one has a long form.
+//! Writing images instead goes through a subcommand, with options of its own.
+
+use anyhow::{bail, Context, Result};
+
+use mark_readelf::elf::image::{FlatBinaryOptions, LoadAddress};
+use mark_readelf::elf::symbol::SymbolLookup;
+use mark_readelf::elf::version::VersionNumber;
+use mark_readelf::elf::Selection;
+
+pub const USAGE: &str = "\
+Usage: mark-readelf <option(s)> elf-file(s)
+       mark-readelf binary <option(s)> elf-file output-file
+       mark-readelf boot-sector <option(s)> elf-file
+       mark-readelf ihex|srec <option(s)> elf-file output-file
+ Display information about the contents of ELF format files
+ Run a subcommand with --help for its options
+ Options are:
+  -a --all               Equivalent to: -h -l -S -s -r -d -V -I -n
+  -h --file-header       Display the ELF file header
+  -l --program-headers   Display the program headers
+     --segments          An alias for --program-headers
+  -S --section-headers   Display the sections' header
+     --sections          An alias for --section-headers
+  -s --syms              Display the symbol table
+     --symbols           An alias for --syms
+  -r --relocs            Display the relocations (if present)
+  -d --dynamic           Display the dynamic section (if present)
+  -V --version-info      Display the version sections (if present)
+  -I --histogram         Display histogram of bucket list lengths
+  -n --notes             Display the core notes (if present)
+     --required-versions Display the newest symbol version needed from each library
+     --version-baseline=<name>
+                         Fail if a library version newer than <name>, e.g.
+                          GLIBC_2.17, is required. Implies --required-versions
+     --lookup=<name>[@<version>]
+                         Look up a dynamic symbol through the hash tables, as
+                          the dynamic linker would
+  -W --wide              Don't split ELF64 program header rows over two lines
+     --lenient           Warn about entry sizes larger than the ELF class
+                          defines, and read on with the sizes the file declares
+     --json              Print the selected dumps as one JSON object per file,
+                          one per line. See src/elf/json.rs for the schema
+  -H --help              Display this information";
+
+pub const BINARY_USAGE: &str = "\
+Usage: mark-readelf binary <option(s)> elf-file output-file
+ Write the loadable segments as a flat binary, like objcopy -O binary
+ Options are:
+     --physical          Lay segments out by physical address (default)
+     --virtual           Lay segments out by virtual address
+     --gap-fill=<byte>   Fill gaps between segments with <byte> (default 0)
+     --start-address=<address>
+                         Start the image at <address> instead of the lowest
+                          segment, leaving out anything below it
+     --pad-to=<size>     Pad the image with the gap fill to <size> bytes
+  -H --help              Display this information
+ Numbers may be decimal or 0x-prefixed hex";
+
+pub const BOOT_SECTOR_USAGE: &str = "\
+Usage: mark-readelf boot-sector <option(s)> elf-file
+ Check that the loadable segments fit a BIOS boot sector at 0x7C00: 510 bytes
+ with the entry point in them, then the 0x55 0xAA signature
+ Options are:
+     --physical          Check segments by physical address (default)
+     --virtual           Check segments by virtual address
+     --add-signature=<output-file>
+                         Write the 512-byte sector to <output-file>, with the
+                          signature added if it's missing
+  -H --help              Display this information";
+
+pub const HEX_USAGE: &str = "\
+Usage: mark-readelf ihex|srec <option(s)> elf-file output-file
+ Write the loadable segments as Intel HEX or Motorola S-records, with the entry
+ point as the start address
+ Options are:
+     --physical          Place segments at their physical address (LMA, default)
+     --virtual           Place segments at their virtual address (VMA)
+  -H --help              Display this information";
+
+/// The dump, or a subcommand
+#[derive(Debug)]
+pub enum Command {
+    Dump(Options),
+    Binary(BinaryOptions),
+    BootSector(BootSectorOptions),
+    IntelHex(HexOptions),
+    Srec(HexOptions),
+}
+
+/// Parse the arguments following the program name, picking the subcommand if
+/// the first one names it
+pub fn parse_command<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("binary") => Ok(Command::Binary(BinaryOptions::parse(args.skip(1))?)),
+        Some("boot-sector") => Ok(Command::BootSector(BootSectorOptions::parse(args.skip(1))?)),
+        Some("ihex") => Ok(Command::IntelHex(HexOptions::parse(args.skip(1))?)),
+        Some("srec") => Ok(Command::Srec(HexOptions::parse(args.skip(1))?)),
+        _ => Ok(Command::Dump(parse_args(args)?)),
+    }
+}
+
+/// A decimal or 0x-prefixed hex number
+fn parse_number(value: &str) -> Result<u64> {
+    let number = match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    number.with_context(|| format!("invalid number '{value}'"))
+}
+
+/// The value of an option that takes one: given after `=`, or else the next argument,
+/// the way getopt_long reads them
+fn option_value(
+    option: &str,
+    value: Option<&str>,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<String> {
+    match value {
+        Some(value) => Ok(value.to_string()),
+        None => args
+            .next()
+            .with_context(|| format!("option '{option}' requires an argument")),
+    }
+}
+
+/// Options for `binary`
+#[derive(Debug, Default)]
+pub struct BinaryOptions {
+    pub image: FlatBinaryOptions,
+    pub help: bool,
+    pub files: Vec<String>, // The ELF file, then the output
+}
+
+impl BinaryOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<BinaryOptions> {
+        let mut options = BinaryOptions::default();
+        while let Some(arg) = args.next() {
+            let (option, value) = match arg.split_once('=') {
+                Some((option, value)) => (option, Some(value)),
+                None => (arg.as_str(), None),
+            };
+            match (option, value) {
+                ("--physical", None) => options.image.load_address = LoadAddress::Physical,
+                ("--virtual", None) => options.image.load_address = LoadAddress::Virtual,
+                ("--gap-fill", value) => {
+                    let value = option_value(option, value, &mut args)?;
+                    let gap_fill = parse_number(&value)?;
+                    options.image.gap_fill = u8::try_from(gap_fill)
+                        .with_context(|| format!("gap fill '{value}' isn't a byte"))?;
+                }
+                ("--start-address", value) => {
+                    let value = option_value(option, value, &mut args)?;
+                    options.image.start_address = Some(parse_number(&value)?)
+                }
+                ("--pad-to", value) => {
+                    let value = option_value(option, value, &mut args)?;
+                    options.image.pad_to = Some(parse_number(&value)?)
+                }
+                ("-H" | "--help", None) => options.help = true,
+                _ if !arg.starts_with('-') || arg == "-" => options.files.push(arg),
+                _ => bail!("unrecognized option '{arg}'"),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Options for `boot-sector`
+#[derive(Debug, Default)]
+pub struct BootSectorOptions {
+    pub load_address: LoadAddress,
+    pub add_signature: Option<String>, // Where to write the fixed sector
+    pub help: bool,
+    pub files: Vec<String>,
+}
+
+impl BootSectorOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<BootSectorOptions> {
+        let mut options = BootSectorOptions::default();
+        while let Some(arg) = args.next() {
+            let (option, value) = match arg.split_once('=') {
+                Some((option, value)) => (option, Some(value)),
+                None => (arg.as_str(), None),
+            };
+            match (option, value) {
+                ("--physical", None) => options.load_address = LoadAddress::Physical,
+                ("--virtual", None) => options.load_address = LoadAddress::Virtual,
+                ("--add-signature", value) => {
+                    options.add_signature = Some(option_value(option, value, &mut args)?)
+                }
+                ("-H" | "--help", None) => options.help = true,
+                _ if !arg.starts_with('-') || arg == "-" => options.files.push(arg),
+                _ => bail!("unrecognized option '{arg}'"),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Options for `ihex` and `srec`
+#[derive(Debug, Default)]
+pub struct HexOptions {
+    pub load_address: LoadAddress,
+    pub help: bool,
+    pub files: Vec<String>, // The ELF file, then the output
+}
+
+impl HexOptions {
+    fn parse(args: impl Iterator<Item = String>) -> Result<HexOptions> {
+        let mut options = HexOptions::default();
+        for arg in args {
+            match arg.as_str() {
+                "--physical" => options.load_address = LoadAddress::Physical,
+                "--virtual" => options.load_address = LoadAddress::Virtual,
+                "-H" | "--help" => options.help = true,
+                _ if !arg.starts_with('-') || arg == "-" => options.files.push(arg),
+                _ => bail!("unrecognized option '{arg}'"),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// What the user asked us to dump, and from which files
+#[derive(Debug, Default)]
+pub struct Options {
+    pub selection: Selection,
+    pub version_baselines: Vec<VersionNumber>, // At most one per version family
+    pub wide: bool,
+    pub json: bool,
+    pub lenient: bool,
+    pub help: bool,
+    pub files: Vec<String>,
+}
+
+impl Options {
+    fn apply_short(&mut self, flag: char) -> Result<()> {
+        match flag {
+            'a' => self.selection.select_all(),
+            'h' => self.selection.file_header = true,
+            'l' => self.selection.program_headers = true,
+            'S' => self.selection.section_headers = true,
+            's' => self.selection.symbols = true,
+            'r' => self.selection.relocations = true,
+            'd' => self.selection.dynamic = true,
+            'V' => self.selection.version_info = true,
+            'I' => self.selection.histogram = true,
+            'n' => self.selection.notes = true,
+            'W' => self.wide = true,
+            'H' => self.help = true,
+            flag => bail!("invalid option -- '{flag}'"),
+        }
+        Ok(())
+    }
+
+    fn apply_long(&mut self, option: &str) -> Result<()> {
+        match option {
+            "all" => self.selection.select_all(),
+            "file-header" => self.selection.file_header = true,
+            "program-headers" | "segments" => self.selection.program_headers = true,
+            "section-headers" | "sections" => self.selection.section_headers = true,
+            "syms" | "symbols" => self.selection.symbols = true,
+            "relocs" => self.selection.relocations = true,
+            "dynamic" => self.selection.dynamic = true,
+            "version-info" => self.selection.version_info = true,
+            "histogram" => self.selection.histogram = true,
+            "notes" => self.selection.notes = true,
+            "required-versions" => self.selection.required_versions = true,
+            "wide" => self.wide = true,
+            "json" => self.json = true,
+            "lenient" => self.lenient = true,
+            "help" => self.help = true,
+            option => bail!("unrecognized option '--{option}'"),
+        }
+        Ok(())
+    }
+
+    fn apply_long_with_value(&mut self, option: &str, value: &str) -> Result<()> {
+        match option {
+            "version-baseline" => {
+                let Some(baseline) = VersionNumber::parse(value) else {
+                    bail!("invalid version baseline '{value}', expected a name like GLIBC_2.17");
+                };
+                self.version_baselines
+                    .retain(|version| version.family != baseline.family);
+                self.version_baselines.push(baseline);
+                self.selection.required_versions = true;
+            }
+            "lookup" => self.selection.lookups.push(SymbolLookup::parse(value)),
+            option => bail!("unrecognized option '--{option}={value}'"),
+        }
+        Ok(())
+    }
+}
+
+/// Long options of the dump that take a value
+const VALUED_OPTIONS: [&str; 2] = ["version-baseline", "lookup"];
+
+/// Parse the arguments following the program name
+pub fn parse_args<I>(args: I) -> Result<Options>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut only_files = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if only_files || arg == "-" || !arg.starts_with('-') {
+            options.files.push(arg);
+        } else if arg == "--" {
+            only_files = true;
+        } else if let Some(option) = arg.strip_prefix("--") {
+            match option.split_once('=') {
+                Some((option, value)) => options.apply_long_with_value(option, value)?,
+                None if VALUED_OPTIONS.contains(&option) => {
+                    let value = option_value(&arg, None, &mut args)?;
+                    options.apply_long_with_value(option, &value)?;
+                }
+                None => options.apply_long(option)?,
+            }
+        } else {
+            for flag in arg.chars().skip(1) {
+                options.apply_short(flag)?;
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn value_after_equals_or_in_the_next_argument() {
+        for lookup in [
+            &["--lookup=malloc", "a.out"][..],
+            &["--lookup", "malloc", "a.out"],
+        ] {
+            let options = parse_args(args(lookup)).unwrap();
+            assert_eq!(options.selection.lookups, [SymbolLookup::parse("malloc")]);
+            assert_eq!(options.files, ["a.out"]);
+        }
+
+        let options = parse_args(args(&["--version-baseline", "GLIBC_2.17", "a.out"])).unwrap();
+        assert_eq!(
+            options.version_baselines,
+            [VersionNumber::parse("GLIBC_2.17").unwrap()]
+        );
+
+        let options = BinaryOptions::parse(args(&[
+            "--gap-fill",
+            "0xff",
+            "--start-address=0x1000",
+            "--pad-to",
+            "512",
+        ]))
+        .unwrap();
+        assert_eq!(options.image.gap_fill, 0xff);
+        assert_eq!(options.image.start_address, Some(0x1000));
+        assert_eq!(options.image.pad_to, Some(512));
+
+        let options =
+            BootSectorOptions::parse(args(&["--add-signature", "-", "boot.elf"])).unwrap();
+        assert_eq!(options.add_signature.as_deref(), Some("-"));
+        assert_eq!(options.files, ["boot.elf"]);
+    }
+
+    #[test]
+    fn missing_value() {
+        assert!(parse_args(args(&["--lookup"])).is_err());
+        assert!(BinaryOptions::parse(args(&["--pad-to"])).is_err());
+        assert!(BootSectorOptions::parse(args(&["--add-signature"])).is_err());
+    }
+}
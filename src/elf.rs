@@ -1,24 +1,140 @@
-use std::cmp;
-use std::fmt::Debug;
-
-use crate::elf::header::ElfHeader;
+use crate::elf::dynamic::{DynamicEntry, DynamicSection};
+use crate::elf::hash::{GnuHashTable, HashTable};
+use crate::elf::header::{Class, ElfHeader, FileType};
+use crate::elf::note::{Note, NoteTable};
 use crate::elf::program_header::ProgramHeader;
+use crate::elf::relocation::{Relocation, RelocationTable};
+use crate::elf::section_header::SectionHeader;
+use crate::elf::symbol::{Symbol, SymbolLookup, SymbolTable};
+use crate::elf::version::{
+    TooNewVersion, VersionDefinitionTable, VersionNeedTable, VersionNumber, VersionSymbolTable,
+};
+use crate::parse::{ParseError, Parser};
+use crate::{abi, to_str};
 
+pub mod address;
+pub mod boot_sector;
+pub mod dynamic;
+pub mod hash;
 pub mod header;
+pub mod hex;
+pub mod image;
+pub mod json;
+pub mod note;
 pub mod program_header;
+pub mod relocation;
+pub mod section_header;
+pub mod symbol;
+#[cfg(test)]
+pub(crate) mod testing;
+pub mod version;
+
+/// Which dumps to show, as the command line selects them
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub file_header: bool,
+    pub program_headers: bool,
+    pub section_headers: bool,
+    pub symbols: bool,
+    pub relocations: bool,
+    pub dynamic: bool,
+    pub version_info: bool,
+    pub histogram: bool,
+    pub notes: bool,
+    pub required_versions: bool,
+    pub lookups: Vec<SymbolLookup>,
+}
+
+impl Selection {
+    /// True if at least one dump was selected
+    pub fn any(&self) -> bool {
+        self.file_header
+            || self.program_headers
+            || self.section_headers
+            || self.symbols
+            || self.relocations
+            || self.dynamic
+            || self.version_info
+            || self.histogram
+            || self.notes
+            || self.required_versions
+            || !self.lookups.is_empty()
+    }
+
+    /// What `-a` shows
+    pub fn select_all(&mut self) {
+        self.file_header = true;
+        self.program_headers = true;
+        self.section_headers = true;
+        self.symbols = true;
+        self.relocations = true;
+        self.dynamic = true;
+        self.version_info = true;
+        self.histogram = true;
+        self.notes = true;
+    }
+}
+
+/// A dynamic symbol's version, as readelf shows it after the symbol's name
+#[derive(Debug, Eq, PartialEq)]
+enum SymbolVersion<'a> {
+    /// `@@NAME`, the version a definition gets by default
+    Default(&'a str),
+    /// `@NAME`, a definition only reachable by naming its version
+    Hidden(&'a str),
+    /// `@NAME (index)`, a version needed from another file
+    Needed(&'a str, u16),
+}
+
+/// Hex the way printf's `%#0Nx` writes it, which leaves the 0x off zero
+fn c_alternate_hex(value: u64, width: usize) -> String {
+    match value {
+        0 => "0".repeat(width),
+        value => format!("{value:#0width$x}"),
+    }
+}
+
+/// Cut long names short to keep the columns lined up, as readelf does
+fn truncate_name(name: &str, width: usize) -> String {
+    match name.char_indices().nth(width) {
+        Some(_) => {
+            let kept = name.chars().take(width.saturating_sub(5));
+            format!("{}[...]", kept.collect::<String>())
+        }
+        None => name.to_string(),
+    }
+}
 
+/// A parsed ELF file. Tables that refer to a section do so by its index in
+/// `section_header_table`. The `print_*` methods write the matching readelf dump to stdout.
 pub struct Elf {
     pub header: ElfHeader,
     pub program_header_table: Vec<ProgramHeader>,
+    pub section_header_table: Vec<SectionHeader>, // Empty if the file has no sections
+    pub symbol_tables: Vec<SymbolTable>,
+    pub relocation_tables: Vec<RelocationTable>,
+    pub dynamic: Option<DynamicSection>,
+    pub version_definitions: Option<VersionDefinitionTable>,
+    pub version_needs: Option<VersionNeedTable>,
+    pub version_symbols: Option<VersionSymbolTable>,
+    pub hash_table: Option<HashTable>,
+    pub gnu_hash_table: Option<GnuHashTable>,
+    pub note_tables: Vec<NoteTable>,
+    /// Problems read past: the ones `Validation::Lenient` allows, and names that couldn't
+    /// be read, which are `<corrupt>` instead
+    pub warnings: Vec<ParseError>,
 }
 
 impl Elf {
     fn print_program_header_table_prelude(&self) {
-        println!("Elf file type is {0:?}, ({0})", self.header.file_type);
-        println!("Entry point {:#X}", self.header.entry);
+        println!("Elf file type is {}", self.header.file_type_description());
+        println!("Entry point {:#x}", self.header.entry);
+        let count = self.program_header_table.len();
+        let plural = if count == 1 { "is" } else { "are" };
+        let headers = if count == 1 { "header" } else { "headers" };
         println!(
-            "There are {} program headers, starting at offset {}",
-            self.header.program_header_entries, self.header.program_header_offset
+            "There {plural} {count} program {headers}, starting at offset {}",
+            self.header.program_header_offset
         );
         println!();
     }
@@ -27,43 +143,1356 @@ impl Elf {
         println!("{}", self.header);
     }
 
-    pub fn print_program_header_table(&self, include_prelude: bool) {
+    /// `-l`. The prelude gives the file type and entry point, for when `-h` wasn't shown.
+    /// ELF64 rows take two lines, unless `wide`.
+    pub fn print_program_header_table(&self, include_prelude: bool, wide: bool) {
+        if self.program_header_table.is_empty() {
+            println!("There are no program headers in this file.");
+            return;
+        }
         if include_prelude {
             self.print_program_header_table_prelude();
         }
 
-        let mut rows = vec![(
-            "Type".to_string(),
-            "Offset   VirtAddr   PhysAddr   FileSiz MemSiz  Flg Align".to_string(),
-        )];
+        for line in self.program_header_lines(wide) {
+            println!("{line}");
+        }
+
+        if !self.section_header_table.is_empty() {
+            println!();
+            self.print_section_to_segment_mapping();
+        }
+    }
+
+    /// The `-l` table itself, from its title to the last row
+    fn program_header_lines(&self, wide: bool) -> Vec<String> {
+        let mut lines = vec!["Program Headers:".to_string()];
+        match (self.header.class, wide) {
+            (Class::Elf32, _) => lines.push(
+                "  Type           Offset   VirtAddr   PhysAddr   FileSiz MemSiz  Flg Align".to_string(),
+            ),
+            (Class::Elf64, true) => lines.push(
+                "  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align".to_string(),
+            ),
+            (Class::Elf64, false) => {
+                lines.push("  Type           Offset             VirtAddr           PhysAddr".to_string());
+                lines.push("                 FileSiz            MemSiz              Flags  Align".to_string());
+            }
+        }
 
         for ph in self.program_header_table.iter() {
-            let header_type = format!("{:?}", ph.header_type);
-            let data = format!(
-                "{:#08X} {:#010X} {:#010X} {:#07X} {:#07X} {:#03X} {:#06X}",
-                ph.offset,
-                ph.virtual_address,
-                ph.physical_address,
-                ph.size_in_file,
-                ph.size_in_memory,
-                ph.flags,
-                ph.alignment
+            // readelf cuts long type names off at 14 characters
+            let header_type = ph.header_type.name(self.header.machine.0);
+            let header_type = header_type.get(..14).unwrap_or(&header_type);
+            match (self.header.class, wide) {
+                (Class::Elf32, _) => lines.push(format!(
+                    "  {header_type:<14} {:#08x} {:#010x} {:#010x} {:#07x} {:#07x} {} {:#x}",
+                    ph.offset,
+                    ph.virtual_address,
+                    ph.physical_address,
+                    ph.size_in_file,
+                    ph.size_in_memory,
+                    ph.flags,
+                    ph.alignment
+                )),
+                // Offsets and sizes are only padded to 6 digits, and grow as needed
+                (Class::Elf64, true) => lines.push(format!(
+                    "  {header_type:<14} {:#08x} {:#018x} {:#018x} {:#08x} {:#08x} {} {:#x}",
+                    ph.offset,
+                    ph.virtual_address,
+                    ph.physical_address,
+                    ph.size_in_file,
+                    ph.size_in_memory,
+                    ph.flags,
+                    ph.alignment
+                )),
+                (Class::Elf64, false) => {
+                    lines.push(format!(
+                        "  {header_type:<14} {:#018x} {:#018x} {:#018x}",
+                        ph.offset, ph.virtual_address, ph.physical_address
+                    ));
+                    lines.push(format!(
+                        "                 {:#018x} {:#018x}  {}    {:#x}",
+                        ph.size_in_file, ph.size_in_memory, ph.flags, ph.alignment
+                    ));
+                }
+            }
+        }
+        lines
+    }
+
+    fn print_section_to_segment_mapping(&self) {
+        println!(" Section to Segment mapping:");
+        println!("  Segment Sections...");
+        for (idx, ph) in self.program_header_table.iter().enumerate() {
+            let sections: String = self
+                .sections_in_segment(ph)
+                .map(|section| format!("{} ", section.name))
+                .collect();
+            println!("   {idx:02}     {sections}");
+        }
+    }
+
+    fn sections_in_segment<'a>(
+        &'a self,
+        ph: &'a ProgramHeader,
+    ) -> impl Iterator<Item = &'a SectionHeader> {
+        // Section 0 is the reserved null entry, which never belongs to a segment
+        self.section_header_table
+            .iter()
+            .skip(1)
+            .filter(|section| ph.contains_section(section))
+    }
+
+    fn print_section_header_table_prelude(&self) {
+        println!(
+            "There are {} section headers, starting at offset {:#x}:",
+            self.section_header_table.len(),
+            self.header.section_header_offset
+        );
+        println!();
+    }
+
+    /// `-S`. The prelude gives the section count and offset, for when `-h` wasn't shown.
+    pub fn print_section_header_table(&self, include_prelude: bool) {
+        if self.section_header_table.is_empty() {
+            println!("There are no sections in this file.");
+            return;
+        }
+        if include_prelude {
+            self.print_section_header_table_prelude();
+        }
+
+        println!("Section Headers:");
+        match self.header.class {
+            Class::Elf32 => {
+                println!("  [Nr] Name              Type            Addr     Off    Size   ES Flg Lk Inf Al");
+            }
+            Class::Elf64 => {
+                println!("  [Nr] Name              Type             Address           Offset");
+                println!("       Size              EntSize          Flags  Link  Info  Align");
+            }
+        }
+
+        for (idx, sh) in self.section_header_table.iter().enumerate() {
+            let name = truncate_name(&sh.name, 17);
+            let flags = to_str::sh_flags_to_string(sh.flags);
+            match self.header.class {
+                Class::Elf32 => println!(
+                    "  [{idx:>2}] {name:<17} {:<15} {:08x} {:06x} {:06x} {:02x} {flags:>3} {:>2} {:>3} {:>2}",
+                    sh.type_name(),
+                    sh.address,
+                    sh.offset,
+                    sh.size,
+                    sh.entry_size,
+                    sh.link,
+                    sh.info,
+                    sh.alignment
+                ),
+                Class::Elf64 => {
+                    println!(
+                        "  [{idx:>2}] {name:<17} {:<16} {:016x}  {:08x}",
+                        sh.type_name(),
+                        sh.address,
+                        sh.offset
+                    );
+                    println!(
+                        "       {:016x}  {:016x} {flags:>3}   {:>5} {:>5}     {}",
+                        sh.size, sh.entry_size, sh.link, sh.info, sh.alignment
+                    );
+                }
+            }
+        }
+
+        println!("Key to Flags:");
+        println!("  W (write), A (alloc), X (execute), M (merge), S (strings), I (info),");
+        println!("  L (link order), O (extra OS processing required), G (group), T (TLS),");
+        println!("  C (compressed), x (unknown), o (OS specific), E (exclude),");
+        println!("  p (processor specific)");
+    }
+
+    /// Section symbols are usually unnamed, so readelf names them after their section
+    fn symbol_display_name<'a>(&'a self, symbol: &'a Symbol) -> &'a str {
+        let section = self.section_header_table.get(symbol.section_index as usize);
+        match section {
+            Some(section) if symbol.symbol_type() == abi::STT_SECTION && symbol.name.is_empty() => {
+                &section.name
+            }
+            _ => &symbol.name,
+        }
+    }
+
+    pub fn print_symbol_tables(&self) {
+        if self.symbol_tables.is_empty() {
+            println!("There are no symbol tables in this file.");
+            return;
+        }
+
+        for (table_idx, table) in self.symbol_tables.iter().enumerate() {
+            if table_idx > 0 {
+                println!();
+            }
+            let section_name = &self.section_header_table[table.section_index].name;
+            println!(
+                "Symbol table '{section_name}' contains {} entries:",
+                table.symbols.len()
             );
-            rows.push((header_type, data));
+            match self.header.class {
+                Class::Elf32 => println!("   Num:    Value  Size Type    Bind   Vis      Ndx Name"),
+                Class::Elf64 => {
+                    println!("   Num:    Value          Size Type    Bind   Vis      Ndx Name")
+                }
+            }
+
+            for (idx, symbol) in table.symbols.iter().enumerate() {
+                // The version shares the name's column, so the name gets what's left of it
+                let version = self.symbol_version_suffix(table, idx).unwrap_or_default();
+                let name_width = 21usize.saturating_sub(version.len());
+                let name = truncate_name(self.symbol_display_name(symbol), name_width) + &version;
+                let value = match self.header.class {
+                    Class::Elf32 => format!("{:08x}", symbol.value),
+                    Class::Elf64 => format!("{:016x}", symbol.value),
+                };
+                println!(
+                    "{idx:>6}: {value} {:>5} {:<7} {:<6} {:<7} {:>4} {name}",
+                    symbol.size,
+                    symbol.type_name(),
+                    symbol.binding_name(),
+                    symbol.visibility_name(),
+                    symbol.section_index_name(),
+                );
+            }
         }
+    }
 
-        let header_type_padding = rows
+    /// The Name/Value column of the dynamic section, decoded the way readelf does
+    fn dynamic_entry_value(&self, entry: &DynamicEntry) -> String {
+        match (entry.tag, &entry.string) {
+            (abi::DT_NEEDED, Some(string)) => format!("Shared library: [{string}]"),
+            (abi::DT_SONAME, Some(string)) => format!("Library soname: [{string}]"),
+            (abi::DT_RPATH, Some(string)) => format!("Library rpath: [{string}]"),
+            (abi::DT_RUNPATH, Some(string)) => format!("Library runpath: [{string}]"),
+            (abi::DT_FLAGS, _) => to_str::d_flags_to_string(entry.value),
+            (abi::DT_FLAGS_1, _) => format!("Flags: {}", to_str::d_flags_1_to_string(entry.value)),
+            (abi::DT_PLTREL, _) => match entry.value as i64 {
+                abi::DT_REL => "REL".to_string(),
+                abi::DT_RELA => "RELA".to_string(),
+                value => format!("{value:#x}"),
+            },
+            (
+                abi::DT_PLTRELSZ
+                | abi::DT_RELASZ
+                | abi::DT_RELAENT
+                | abi::DT_STRSZ
+                | abi::DT_SYMENT
+                | abi::DT_RELSZ
+                | abi::DT_RELENT
+                | abi::DT_INIT_ARRAYSZ
+                | abi::DT_FINI_ARRAYSZ
+                | abi::DT_PREINIT_ARRAYSZ
+                | abi::DT_GNU_CONFLICTSZ
+                | abi::DT_GNU_LIBLISTSZ
+                | abi::DT_PLTPADSZ
+                | abi::DT_MOVEENT
+                | abi::DT_MOVESZ
+                | abi::DT_SYMINSZ
+                | abi::DT_SYMINENT,
+                _,
+            ) => format!("{} (bytes)", entry.value),
+            (abi::DT_RELACOUNT | abi::DT_RELCOUNT | abi::DT_VERDEFNUM | abi::DT_VERNEEDNUM, _) => {
+                entry.value.to_string()
+            }
+            _ => format!("{:#x}", entry.value),
+        }
+    }
+
+    pub fn print_dynamic_section(&self) {
+        let Some(dynamic) = &self.dynamic else {
+            println!("There is no dynamic section in this file.");
+            return;
+        };
+
+        println!(
+            "Dynamic section at offset {:#x} contains {} entries:",
+            dynamic.offset,
+            dynamic.entries.len()
+        );
+        println!("  Tag        Type                         Name/Value");
+        for entry in dynamic.entries.iter() {
+            // Both classes line the values up in the same column
+            let (tag, tag_name_width) = match self.header.class {
+                Class::Elf32 => (format!("{:#010x}", entry.tag as u32), 28),
+                Class::Elf64 => (format!("{:#018x}", entry.tag), 20),
+            };
+            let tag_name = format!("({})", entry.tag_name());
+            let value = self.dynamic_entry_value(entry);
+            println!(" {tag} {tag_name:<tag_name_width$} {value}");
+        }
+    }
+
+    pub fn print_relocation_tables(&self) {
+        if self.relocation_tables.is_empty() {
+            println!("There are no relocations in this file.");
+            return;
+        }
+
+        for (table_idx, table) in self.relocation_tables.iter().enumerate() {
+            if table_idx > 0 {
+                println!();
+            }
+            let section = &self.section_header_table[table.section_index];
+            let has_addend = section.section_type == abi::SHT_RELA;
+            let entries = match table.relocations.len() {
+                1 => "1 entry".to_string(),
+                count => format!("{count} entries"),
+            };
+            println!(
+                "Relocation section '{}' at offset {:#x} contains {entries}:",
+                section.name, section.offset
+            );
+            match (self.header.class, has_addend) {
+                (Class::Elf32, false) => {
+                    println!(" Offset     Info    Type            Sym.Value  Sym. Name")
+                }
+                (Class::Elf32, true) => {
+                    println!(" Offset     Info    Type            Sym.Value  Sym. Name + Addend")
+                }
+                (Class::Elf64, false) => {
+                    println!("  Offset          Info           Type           Sym. Value    Sym. Name")
+                }
+                (Class::Elf64, true) => println!(
+                    "  Offset          Info           Type           Sym. Value    Sym. Name + Addend"
+                ),
+            }
+
+            // The section's sh_link names the symbol table its symbol indexes refer to
+            let symbol_table = self
+                .symbol_tables
+                .iter()
+                .find(|symbol_table| symbol_table.section_index == section.link as usize);
+
+            for relocation in table.relocations.iter() {
+                println!("{}", self.relocation_line(relocation, symbol_table));
+            }
+        }
+    }
+
+    /// One row of a relocation table, with the symbol looked up in `symbol_table`
+    fn relocation_line(
+        &self,
+        relocation: &Relocation,
+        symbol_table: Option<&SymbolTable>,
+    ) -> String {
+        let relocation_type =
+            to_str::r_type_to_string(self.header.machine.0, relocation.relocation_type);
+        let symbol = match relocation.symbol_index {
+            0 => None,
+            idx => symbol_table.and_then(|table| table.symbols.get(idx as usize)),
+        };
+        let addend = match relocation.addend {
+            Some(addend) if addend < 0 => format!(" - {:x}", addend.unsigned_abs()),
+            Some(addend) => format!(" + {addend:x}"),
+            None => String::new(),
+        };
+
+        // Type names are cut to fit their column, as readelf does
+        let (columns, value_width, separator) = match self.header.class {
+            Class::Elf32 => (
+                format!(
+                    "{:08x}  {:08x} {relocation_type:<17.17}",
+                    relocation.offset, relocation.info
+                ),
+                8,
+                "   ",
+            ),
+            Class::Elf64 => (
+                format!(
+                    "{:012x}  {:012x} {relocation_type:<17.17}",
+                    relocation.offset, relocation.info
+                ),
+                16,
+                " ",
+            ),
+        };
+        match symbol {
+            Some(symbol) => {
+                let name = truncate_name(self.symbol_display_name(symbol), 22);
+                format!(
+                    "{columns} {:0value_width$x}{separator}{name}{addend}",
+                    symbol.value
+                )
+            }
+            None => {
+                // No symbol, so only the addend is left to show
+                let addend = relocation
+                    .addend
+                    .map(|addend| format!("{addend:x}"))
+                    .unwrap_or_default();
+                let padding = " ".repeat(value_width + 3);
+                format!("{columns} {padding}{addend}")
+            }
+        }
+    }
+
+    /// Note type as readelf describes it. Only core files use the core note types.
+    fn note_type_name(&self, note: &Note) -> String {
+        let name = match note.name.as_str() {
+            "GNU" => to_str::nt_gnu_to_str(note.note_type),
+            _ if self.header.file_type == FileType::Core => to_str::nt_core_to_str(note.note_type),
+            _ => None,
+        };
+        match name {
+            Some(name) => name.to_string(),
+            None => format!("Unknown note type: ({:#010x})", note.note_type),
+        }
+    }
+
+    /// The lines readelf prints under a note, decoding the descriptors of the GNU notes
+    fn note_description(&self, note: &Note) -> Vec<String> {
+        let mut parser = Parser::new(&note.descriptor, self.header.class, self.header.endianness);
+        match (note.name.as_str(), note.note_type) {
+            ("GNU", abi::NT_GNU_BUILD_ID) => {
+                let build_id: String = note
+                    .descriptor
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                vec![format!("    Build ID: {build_id}")]
+            }
+            ("GNU", abi::NT_GNU_ABI_TAG) => {
+                let words = (0..4)
+                    .map(|_| parser.parse_u32())
+                    .collect::<Result<Vec<_>, _>>();
+                match words.as_deref() {
+                    Ok(&[os, major, minor, subminor]) => {
+                        let os = to_str::note_abi_tag_os_to_str(os).unwrap_or("Unknown");
+                        vec![format!("    OS: {os}, ABI: {major}.{minor}.{subminor}")]
+                    }
+                    _ => vec!["    <corrupt GNU_ABI_TAG>".to_string()],
+                }
+            }
+            ("GNU", abi::NT_GNU_GOLD_VERSION) => {
+                let version = String::from_utf8_lossy(&note.descriptor);
+                vec![format!("    Version: {}", version.trim_end_matches('\0'))]
+            }
+            ("GNU", abi::NT_GNU_HWCAP) => match (parser.parse_u32(), parser.parse_u32()) {
+                (Ok(entries), Ok(mask)) => vec![format!(
+                    "    Hardware Capabilities: num entries: {entries}, enabled mask: {mask:x}"
+                )],
+                _ => vec!["    <corrupt GNU_HWCAP>".to_string()],
+            },
+            ("GNU", abi::NT_GNU_PROPERTY_TYPE_0) => self.gnu_property_description(note),
+            _ if note.descriptor.is_empty() => Vec::new(),
+            _ => {
+                let data: String = note
+                    .descriptor
+                    .iter()
+                    .map(|byte| format!("{byte:02x} "))
+                    .collect();
+                vec![format!("   description data: {data}")]
+            }
+        }
+    }
+
+    /// The properties of an NT_GNU_PROPERTY_TYPE_0 note, one per line as readelf shows them
+    fn gnu_property_description(&self, note: &Note) -> Vec<String> {
+        // Properties are padded to the size of an address
+        let size = match self.header.class {
+            Class::Elf32 => 4,
+            Class::Elf64 => 8,
+        };
+        let descriptor = &note.descriptor;
+        if descriptor.len() < 8 || !descriptor.len().is_multiple_of(size) {
+            return vec![format!(
+                "      Properties: <corrupt GNU_PROPERTY_TYPE, size = {:#x}>",
+                descriptor.len()
+            )];
+        }
+
+        let is_x86 = matches!(
+            self.header.machine.0,
+            abi::EM_X86_64 | abi::EM_386 | abi::EM_IAMCU
+        );
+        let is_aarch64 = self.header.machine.0 == abi::EM_AARCH64;
+        let mut properties = Vec::new();
+        let mut offset = 0;
+        while offset < descriptor.len() {
+            let mut parser = Parser::new_with_offset(
+                descriptor,
+                offset,
+                self.header.class,
+                self.header.endianness,
+            );
+            let (Ok(property_type), Ok(data_size)) = (parser.parse_u32(), parser.parse_u32())
+            else {
+                properties.push(format!("<corrupt descsz: {:#x}>", descriptor.len()));
+                break;
+            };
+            let data_start = offset + 8;
+            let data_end = data_start.checked_add(data_size as usize);
+            let Some(data) = data_end.and_then(|data_end| descriptor.get(data_start..data_end))
+            else {
+                properties.push(format!(
+                    "<corrupt type ({property_type:#x}) datasz: {data_size:#x}>"
+                ));
+                break;
+            };
+            let mut data_parser = Parser::new(data, self.header.class, self.header.endianness);
+            let bitmask = match data_size {
+                4 => data_parser.parse_u32().ok(),
+                _ => None,
+            };
+
+            let label = match property_type {
+                abi::GNU_PROPERTY_X86_FEATURE_1_AND if is_x86 => Some("x86 feature"),
+                abi::GNU_PROPERTY_X86_FEATURE_2_USED if is_x86 => Some("x86 feature used"),
+                abi::GNU_PROPERTY_X86_FEATURE_2_NEEDED if is_x86 => Some("x86 feature needed"),
+                abi::GNU_PROPERTY_X86_ISA_1_USED if is_x86 => Some("x86 ISA used"),
+                abi::GNU_PROPERTY_X86_ISA_1_NEEDED if is_x86 => Some("x86 ISA needed"),
+                abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND if is_aarch64 => Some("AArch64 feature"),
+                _ => None,
+            };
+            let property = match (label, bitmask) {
+                (Some(label), None) => format!("{label}: <corrupt length: {data_size:#x}> "),
+                (Some(label), Some(bits)) => {
+                    let bits = match property_type {
+                        abi::GNU_PROPERTY_X86_FEATURE_1_AND => {
+                            to_str::gnu_property_x86_feature_1_to_string(bits)
+                        }
+                        abi::GNU_PROPERTY_X86_FEATURE_2_USED
+                        | abi::GNU_PROPERTY_X86_FEATURE_2_NEEDED => {
+                            to_str::gnu_property_x86_feature_2_to_string(bits)
+                        }
+                        abi::GNU_PROPERTY_X86_ISA_1_USED | abi::GNU_PROPERTY_X86_ISA_1_NEEDED => {
+                            to_str::gnu_property_x86_isa_1_to_string(bits)
+                        }
+                        _ => to_str::gnu_property_aarch64_feature_1_to_string(bits),
+                    };
+                    format!("{label}: {bits}")
+                }
+                (None, _) => match property_type {
+                    abi::GNU_PROPERTY_STACK_SIZE if data_size as usize == size => {
+                        let stack_size = data_parser.parse_class_sized().unwrap_or_default();
+                        format!("stack size: {stack_size:#x}")
+                    }
+                    abi::GNU_PROPERTY_STACK_SIZE => {
+                        format!("stack size: <corrupt length: {data_size:#x}> ")
+                    }
+                    abi::GNU_PROPERTY_NO_COPY_ON_PROTECTED if data_size == 0 => {
+                        "no copy on protected ".to_string()
+                    }
+                    abi::GNU_PROPERTY_NO_COPY_ON_PROTECTED => {
+                        format!("no copy on protected <corrupt length: {data_size:#x}> ")
+                    }
+                    _ => {
+                        let kind = match property_type {
+                            ..abi::GNU_PROPERTY_LOPROC => "unknown",
+                            abi::GNU_PROPERTY_LOPROC..abi::GNU_PROPERTY_LOUSER => {
+                                "processor-specific"
+                            }
+                            _ => "application-specific",
+                        };
+                        let data: String = data.iter().map(|byte| format!("{byte:02x} ")).collect();
+                        format!("<{kind} type {property_type:#x} data: {data}>")
+                    }
+                },
+            };
+            properties.push(property);
+            offset = (data_start + data.len()).next_multiple_of(size);
+        }
+
+        let mut lines = vec![format!("      Properties: {}", properties[0])];
+        lines.extend(
+            properties[1..]
+                .iter()
+                .map(|property| format!("\t{property}")),
+        );
+        lines
+    }
+
+    /// Name of the version `version` refers to, looking in the definitions, the
+    /// requirements, or both. VER_NDX_HIDDEN is ignored.
+    fn version_name(&self, version: u16, definitions: bool, needs: bool) -> Option<&str> {
+        let index = version & abi::VER_NDX_VERSION;
+        let needed = self
+            .version_needs
+            .iter()
+            .flat_map(|table| table.needs.iter())
+            .flat_map(|need| need.requirements.iter())
+            .find(|requirement| requirement.index == index)
+            .filter(|_| needs)
+            .map(|requirement| requirement.name.as_str());
+        let defined = self
+            .version_definitions
             .iter()
-            .map(|(header_type, _)| header_type.len())
-            .max()
-            .unwrap();
-        let header_type_padding = header_type_padding + 6; // Same as GNU readelf
+            .flat_map(|table| table.definitions.iter())
+            .find(|definition| definition.index == index)
+            .filter(|_| definitions)
+            .and_then(|definition| definition.names.first())
+            .map(|name| name.name.as_str());
+        needed.or(defined)
+    }
 
-        println!("Program Headers:");
-        for (header_type, data) in rows.iter() {
-            let padding = cmp::max(header_type_padding - header_type.len(), 0);
-            let padding = " ".repeat(padding);
-            println!("{header_type}{padding}{data}");
+    /// The version readelf shows after a dynamic symbol's name. Definitions are only
+    /// looked up for defined symbols, and the base definition, which names the file
+    /// itself, isn't shown.
+    fn symbol_version(&self, table: &SymbolTable, symbol_idx: usize) -> Option<SymbolVersion<'_>> {
+        let section = &self.section_header_table[table.section_index];
+        if section.section_type != abi::SHT_DYNSYM {
+            return None;
+        }
+        let version = *self.version_symbols.as_ref()?.versions.get(symbol_idx)?;
+        if version == abi::VER_NDX_LOCAL {
+            return None;
         }
+        let symbol = &table.symbols[symbol_idx];
+        let index = version & abi::VER_NDX_VERSION;
+        let is_hidden = version & abi::VER_NDX_HIDDEN != 0;
+
+        let definition = self
+            .version_definitions
+            .iter()
+            .flat_map(|table| table.definitions.iter())
+            .find(|definition| definition.index == index)
+            .filter(|_| symbol.shndx != abi::SHN_UNDEF && version != abi::VER_NDX_HIDDEN_GLOBAL);
+        if let Some(definition) = definition {
+            if definition.index == abi::VER_NDX_GLOBAL && definition.flags == abi::VER_FLG_BASE {
+                return None;
+            }
+            let name = &definition.names.first()?.name;
+            // Each definition has an absolute symbol of the same name, which needs no suffix
+            if symbol.name == *name {
+                return None;
+            }
+            return match is_hidden {
+                true => Some(SymbolVersion::Hidden(name)),
+                false => Some(SymbolVersion::Default(name)),
+            };
+        }
+        let name = self.version_name(version, false, true)?;
+        Some(SymbolVersion::Needed(name, index))
+    }
+
+    /// The `@VERSION` or `@@VERSION` readelf appends to dynamic symbol names
+    fn symbol_version_suffix(&self, table: &SymbolTable, symbol_idx: usize) -> Option<String> {
+        let suffix = match self.symbol_version(table, symbol_idx)? {
+            SymbolVersion::Default(name) => format!("@@{name}"),
+            SymbolVersion::Hidden(name) => format!("@{name}"),
+            SymbolVersion::Needed(name, index) => format!("@{name} ({index})"),
+        };
+        Some(suffix)
+    }
+
+    /// Name of the version a .gnu.version entry refers to, as `-V` shows it. Undefined
+    /// symbols refer to requirements and defined ones to definitions, but symbols in
+    /// NOBITS sections may be either.
+    fn version_symbol_name(
+        &self,
+        symbols: Option<&SymbolTable>,
+        symbol_idx: usize,
+        version: u16,
+    ) -> Option<&str> {
+        if matches!(version, abi::VER_NDX_LOCAL | abi::VER_NDX_GLOBAL) {
+            return None;
+        }
+        let symbol = symbols?.symbols.get(symbol_idx)?;
+        let is_nobits = self
+            .section_header_table
+            .get(symbol.section_index as usize)
+            .is_some_and(|section| section.section_type == abi::SHT_NOBITS);
+        let is_undefined = symbol.shndx == abi::SHN_UNDEF;
+        let definitions = (is_nobits || !is_undefined) && version != abi::VER_NDX_HIDDEN_GLOBAL;
+        let needs = is_nobits || is_undefined;
+        self.version_name(version, definitions, needs)
+    }
+
+    /// Header shared by the three version sections
+    fn print_version_section_header(&self, section_index: usize, what: &str, entries: usize) {
+        let section = &self.section_header_table[section_index];
+        let link_name = self
+            .section_header_table
+            .get(section.link as usize)
+            .map(|link| link.name.as_str())
+            .unwrap_or("<corrupt>");
+        let entries = match entries {
+            1 => "1 entry".to_string(),
+            count => format!("{count} entries"),
+        };
+        println!("{what} section '{}' contains {entries}:", section.name);
+        // readelf shows a full 64-bit address whatever the class
+        println!(
+            " Addr: {:#018x}  Offset: {:#010x}  Link: {} ({link_name})",
+            section.address, section.offset, section.link
+        );
+    }
+
+    fn print_version_definitions(&self, table: &VersionDefinitionTable) {
+        self.print_version_section_header(
+            table.section_index,
+            "Version definition",
+            table.definitions.len(),
+        );
+        for definition in table.definitions.iter() {
+            let Some((name, parents)) = definition.names.split_first() else {
+                continue;
+            };
+            println!(
+                "  {}: Rev: {}  Flags: {}  Index: {}  Cnt: {}  Name: {}",
+                c_alternate_hex(definition.offset, 6),
+                definition.version,
+                to_str::ver_flags_to_string(definition.flags),
+                definition.index,
+                definition.names.len(),
+                name.name
+            );
+            for (idx, parent) in parents.iter().enumerate() {
+                println!(
+                    "  {}: Parent {}: {}",
+                    c_alternate_hex(parent.offset, 6),
+                    idx + 1,
+                    parent.name
+                );
+            }
+        }
+    }
+
+    fn print_version_needs(&self, table: &VersionNeedTable) {
+        self.print_version_section_header(table.section_index, "Version needs", table.needs.len());
+        for need in table.needs.iter() {
+            println!(
+                "  {}: Version: {}  File: {}  Cnt: {}",
+                c_alternate_hex(need.offset, 6),
+                need.version,
+                need.file,
+                need.requirements.len()
+            );
+            for requirement in need.requirements.iter() {
+                println!(
+                    "  {}:   Name: {}  Flags: {}  Version: {}",
+                    c_alternate_hex(requirement.offset, 6),
+                    requirement.name,
+                    to_str::ver_flags_to_string(requirement.flags),
+                    requirement.index
+                );
+            }
+        }
+    }
+
+    fn print_version_symbols(&self, table: &VersionSymbolTable) {
+        self.print_version_section_header(
+            table.section_index,
+            "Version symbols",
+            table.versions.len(),
+        );
+        let link = self.section_header_table[table.section_index].link as usize;
+        let symbols = self
+            .symbol_tables
+            .iter()
+            .find(|symbol_table| symbol_table.section_index == link);
+
+        for (row_idx, row) in table.versions.chunks(4).enumerate() {
+            let mut line = format!("  {:03x}:", row_idx * 4);
+            for (column_idx, &version) in row.iter().enumerate() {
+                let entry = match version {
+                    abi::VER_NDX_LOCAL => "   0 (*local*)    ".to_string(),
+                    abi::VER_NDX_GLOBAL => "   1 (*global*)   ".to_string(),
+                    _ => {
+                        let hidden = match version & abi::VER_NDX_HIDDEN {
+                            0 => ' ',
+                            _ => 'h',
+                        };
+                        let mut entry = format!("{:4x}{hidden}", version & abi::VER_NDX_VERSION);
+                        let symbol_idx = row_idx * 4 + column_idx;
+                        if let Some(name) = self.version_symbol_name(symbols, symbol_idx, version) {
+                            // readelf pads the name to 12 columns, or past it by as much as
+                            // it overflows
+                            let width = (12 - name.len() as isize).unsigned_abs();
+                            entry += &format!("({name}{:<width$}", ")");
+                        }
+                        format!("{entry:<18}")
+                    }
+                };
+                line += &entry;
+            }
+            println!("{line}");
+        }
+    }
+
+    pub fn print_version_info(&self) {
+        let mut printed_any = false;
+        // readelf shows the version sections in section header order
+        for idx in 0..self.section_header_table.len() {
+            let definitions = self
+                .version_definitions
+                .as_ref()
+                .filter(|table| table.section_index == idx);
+            let needs = self
+                .version_needs
+                .as_ref()
+                .filter(|table| table.section_index == idx);
+            let symbols = self
+                .version_symbols
+                .as_ref()
+                .filter(|table| table.section_index == idx);
+            if definitions.is_none() && needs.is_none() && symbols.is_none() {
+                continue;
+            }
+
+            if printed_any {
+                println!();
+            }
+            printed_any = true;
+            if let Some(table) = definitions {
+                self.print_version_definitions(table);
+            }
+            if let Some(table) = needs {
+                self.print_version_needs(table);
+            }
+            if let Some(table) = symbols {
+                self.print_version_symbols(table);
+            }
+        }
+
+        if !printed_any {
+            println!("No version information found in this file.");
+        }
+    }
+
+    /// The newest numbered version required from each library, per version family. That's
+    /// the oldest release of the library this file will run against.
+    pub fn minimum_library_versions(&self) -> Vec<(&str, VersionNumber)> {
+        let mut result: Vec<(&str, VersionNumber)> = Vec::new();
+        for need in self
+            .version_needs
+            .iter()
+            .flat_map(|table| table.needs.iter())
+        {
+            for requirement in need.requirements.iter() {
+                let Some(version) = VersionNumber::parse(&requirement.name) else {
+                    continue;
+                };
+                let highest = result
+                    .iter_mut()
+                    .find(|(file, highest)| *file == need.file && highest.family == version.family);
+                match highest {
+                    Some((_, highest)) if version > *highest => *highest = version,
+                    Some(_) => {}
+                    None => result.push((&need.file, version)),
+                }
+            }
+        }
+        result
+    }
+
+    /// The minimum library versions that are newer than the baseline for their family.
+    /// Families without a baseline aren't checked.
+    pub fn versions_newer_than<'a>(
+        &'a self,
+        baselines: &'a [VersionNumber],
+    ) -> Vec<TooNewVersion<'a>> {
+        self.minimum_library_versions()
+            .into_iter()
+            .filter_map(|(file, version)| {
+                let baseline = baselines
+                    .iter()
+                    .find(|baseline| baseline.family == version.family)?;
+                (version > *baseline).then_some(TooNewVersion {
+                    file,
+                    version,
+                    baseline,
+                })
+            })
+            .collect()
+    }
+
+    pub fn print_minimum_library_versions(&self) {
+        let versions = self.minimum_library_versions();
+        if versions.is_empty() {
+            println!("There are no library version requirements in this file.");
+            return;
+        }
+
+        println!("Minimum library versions required:");
+        println!("  Library                        Version");
+        for (file, version) in versions.iter() {
+            println!("  {file:<30} {version}");
+        }
+    }
+
+    /// The symbol table a hash table indexes, through its sh_link
+    fn hashed_symbol_table(&self, hash_section_index: usize) -> Option<&SymbolTable> {
+        let link = self.section_header_table[hash_section_index].link as usize;
+        self.symbol_tables
+            .iter()
+            .find(|table| table.section_index == link)
+    }
+
+    /// Whether ld.so would bind a reference to `name`, at `version` if given, to the
+    /// symbol at `symbol_idx`. Without a version, only the default version of a
+    /// versioned symbol is accepted, as dlsym does.
+    fn symbol_matches(
+        &self,
+        table: &SymbolTable,
+        symbol_idx: usize,
+        name: &str,
+        version: Option<&str>,
+    ) -> bool {
+        let Some(symbol) = table.symbols.get(symbol_idx) else {
+            return false;
+        };
+        if symbol.name != name || symbol.shndx == abi::SHN_UNDEF {
+            return false;
+        }
+        let bindable_type = matches!(
+            symbol.symbol_type(),
+            abi::STT_NOTYPE
+                | abi::STT_OBJECT
+                | abi::STT_FUNC
+                | abi::STT_COMMON
+                | abi::STT_TLS
+                | abi::STT_GNU_IFUNC
+        );
+        if !bindable_type || (symbol.value == 0 && symbol.symbol_type() != abi::STT_TLS) {
+            return false;
+        }
+
+        let symbol_version = self
+            .version_symbols
+            .as_ref()
+            .and_then(|versions| versions.versions.get(symbol_idx).copied());
+        let Some(symbol_version) = symbol_version else {
+            return true; // Unversioned objects match any request
+        };
+        let index = symbol_version & abi::VER_NDX_VERSION;
+        let is_hidden = symbol_version & abi::VER_NDX_HIDDEN != 0;
+        match version {
+            Some(version) => {
+                let is_unversioned = index <= abi::VER_NDX_GLOBAL;
+                self.version_name(symbol_version, true, false) == Some(version)
+                    || (is_unversioned && !is_hidden)
+            }
+            None => !is_hidden,
+        }
+    }
+
+    /// Find a dynamic symbol by name the way ld.so does: through .gnu.hash and its bloom
+    /// filter if there is one, otherwise through the SysV .hash table. Returns the symbol's
+    /// index in the dynamic symbol table.
+    pub fn lookup_dynamic_symbol(
+        &self,
+        name: &str,
+        version: Option<&str>,
+    ) -> Option<(usize, &Symbol)> {
+        let (section_index, candidates) = match (&self.gnu_hash_table, &self.hash_table) {
+            (Some(table), _) => (table.section_index, table.candidates(name)),
+            (None, Some(table)) => (table.section_index, table.candidates(name)),
+            (None, None) => return None,
+        };
+        let table = self.hashed_symbol_table(section_index)?;
+        candidates
+            .into_iter()
+            .map(|idx| idx as usize)
+            .find(|&idx| self.symbol_matches(table, idx, name, version))
+            .map(|idx| (idx, &table.symbols[idx]))
+    }
+
+    pub fn print_symbol_lookup(&self, name: &str, version: Option<&str>) {
+        let section_index = match (&self.gnu_hash_table, &self.hash_table) {
+            (Some(table), _) => table.section_index,
+            (None, Some(table)) => table.section_index,
+            (None, None) => {
+                println!("There are no hash tables in this file.");
+                return;
+            }
+        };
+        let request = match version {
+            Some(version) => format!("{name}@{version}"),
+            None => name.to_string(),
+        };
+        println!(
+            "Symbol lookup for '{request}' through `{}':",
+            self.section_header_table[section_index].name
+        );
+        match &self.gnu_hash_table {
+            Some(table) => {
+                let hash = hash::gnu_hash(name.as_bytes());
+                let bloom = match table.bloom_filter_matches(hash) {
+                    true => "may match",
+                    false => "rejects",
+                };
+                println!("  Hash: {hash:#010x}  Bloom filter: {bloom}");
+            }
+            None => println!("  Hash: {:#010x}", hash::elf_hash(name.as_bytes())),
+        }
+
+        let Some((idx, symbol)) = self.lookup_dynamic_symbol(name, version) else {
+            println!("  Not found");
+            return;
+        };
+        let table = self.hashed_symbol_table(section_index);
+        let suffix = table
+            .and_then(|table| self.symbol_version_suffix(table, idx))
+            .unwrap_or_default();
+        println!(
+            "  Found: {}{suffix}  Index: {idx}  Value: {:#x}  Size: {}  Type: {}  Bind: {}",
+            symbol.name,
+            symbol.value,
+            symbol.size,
+            symbol.type_name(),
+            symbol.binding_name()
+        );
+    }
+
+    /// How many buckets hold each number of symbols, from 0 up to the longest chain
+    fn bucket_length_counts(lengths: &[usize]) -> Vec<usize> {
+        let max_length = lengths.iter().copied().max().unwrap_or(0);
+        let mut counts = vec![0usize; max_length + 1];
+        for &length in lengths {
+            counts[length] += 1;
+        }
+        counts
+    }
+
+    /// One `-I` histogram, from the number of symbols in each bucket
+    fn bucket_histogram(title: &str, lengths: &[usize]) -> Vec<String> {
+        let buckets = match lengths.len() {
+            1 => "1 bucket".to_string(),
+            count => format!("{count} buckets"),
+        };
+        let mut lines = vec![
+            format!("Histogram for {title} (total of {buckets}):"),
+            " Length  Number     % of total  Coverage".to_string(),
+        ];
+        if lengths.is_empty() {
+            return lines;
+        }
+
+        let counts = Self::bucket_length_counts(lengths);
+        let symbols: usize = lengths.iter().sum();
+        let percent = |count: usize, total: usize| count as f64 * 100.0 / total as f64;
+
+        lines.push(format!(
+            "      0  {:<10} ({:5.1}%)",
+            counts[0],
+            percent(counts[0], lengths.len())
+        ));
+        let mut covered = 0;
+        for (length, &count) in counts.iter().enumerate().skip(1) {
+            covered += count * length;
+            lines.push(format!(
+                "{length:>7}  {count:<10} ({:5.1}%)    {:5.1}%",
+                percent(count, lengths.len()),
+                percent(covered, symbols)
+            ));
+        }
+        lines
+    }
+
+    pub fn print_histograms(&self) {
+        if let Some(table) = &self.hash_table {
+            for line in Self::bucket_histogram("bucket list length", &table.chain_lengths()) {
+                println!("{line}");
+            }
+        }
+        if let Some(table) = &self.gnu_hash_table {
+            if self.hash_table.is_some() {
+                println!();
+            }
+            let name = &self.section_header_table[table.section_index].name;
+            let title = format!("`{name}' bucket list length");
+            for line in Self::bucket_histogram(&title, &table.chain_lengths()) {
+                println!("{line}");
+            }
+        }
+    }
+
+    pub fn print_notes(&self) {
+        for (table_idx, table) in self.note_tables.iter().enumerate() {
+            if table_idx > 0 {
+                println!();
+            }
+            match table.section_index {
+                Some(section_index) => println!(
+                    "Displaying notes found in: {}",
+                    self.section_header_table[section_index].name
+                ),
+                None => println!(
+                    "Displaying notes found at file offset {:#010x} with length {:#010x}:",
+                    table.offset, table.size
+                ),
+            }
+            println!("  Owner                Data size \tDescription");
+            for note in table.notes.iter() {
+                println!(
+                    "  {:<20} {:#010x}\t{}",
+                    note.name,
+                    note.descriptor.len(),
+                    self.note_type_name(note)
+                );
+                for line in self.note_description(note) {
+                    println!("{line}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::header::Endianness;
+    use crate::elf::program_header::SegmentFlags;
+    use crate::elf::testing::{
+        build_elf, build_elf_with, build_hashed_elf, build_relocation_elf, build_versioned_elf,
+        Segment, HASHED_SYMBOLS,
+    };
+    use crate::elf::version::{VersionNeed, VersionRequirement};
+    use crate::parse::parse_elf;
+
+    /// An ELF that needs each of `requirements`, as (file, version name) pairs
+    fn elf_needing(requirements: &[(&str, &str)]) -> Elf {
+        let mut elf = parse_elf(&build_elf(0, &[])).unwrap();
+        let needs = requirements.iter().map(|&(file, name)| VersionNeed {
+            offset: 0,
+            version: abi::VER_NEED_CURRENT,
+            file: file.to_string(),
+            requirements: vec![VersionRequirement {
+                offset: 0,
+                flags: 0,
+                index: 2,
+                name: name.to_string(),
+            }],
+        });
+        elf.version_needs = Some(VersionNeedTable {
+            section_index: 0,
+            needs: needs.collect(),
+        });
+        elf
+    }
+
+    fn versions(names: &[&str]) -> Vec<VersionNumber> {
+        names
+            .iter()
+            .map(|name| VersionNumber::parse(name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn symbol_version_suffixes() {
+        let elf = parse_elf(&build_versioned_elf()).unwrap();
+        let table = &elf.symbol_tables[0];
+        let suffixes: Vec<Option<String>> = (0..table.symbols.len())
+            .map(|idx| elf.symbol_version_suffix(table, idx))
+            .collect();
+        let expected = [
+            None,
+            Some("@@LIBX_2.0"),
+            Some("@LIBX_2.0"),
+            Some("@GLIBC_2.34 (3)"),
+            None, // The version's own absolute symbol
+            None, // The base definition
+            None, // A hidden global
+        ];
+        assert_eq!(suffixes, expected.map(|suffix| suffix.map(str::to_string)));
+    }
+
+    #[test]
+    fn minimum_library_versions() {
+        let elf = elf_needing(&[
+            ("libc.so.6", "GLIBC_2.2.5"),
+            ("libc.so.6", "GLIBC_2.17"),
+            ("libc.so.6", "GLIBC_2.3"),
+            ("libc.so.6", "GLIBC_PRIVATE"),
+            ("libgcc_s.so.1", "GCC_3.0"),
+            ("libgcc_s.so.1", "GLIBC_2.2.5"),
+        ]);
+        let minimums: Vec<(&str, String)> = elf
+            .minimum_library_versions()
+            .into_iter()
+            .map(|(file, version)| (file, version.to_string()))
+            .collect();
+        // Highest by number, per file and family, with GLIBC_PRIVATE left out
+        let expected = [
+            ("libc.so.6", "GLIBC_2.17"),
+            ("libgcc_s.so.1", "GCC_3.0"),
+            ("libgcc_s.so.1", "GLIBC_2.2.5"),
+        ];
+        assert_eq!(
+            minimums,
+            expected.map(|(file, version)| (file, version.to_string()))
+        );
+    }
+
+    #[test]
+    fn versions_newer_than_the_baseline() {
+        let elf = elf_needing(&[
+            ("libc.so.6", "GLIBC_2.17"),
+            ("libgcc_s.so.1", "GCC_7.0.0"),
+            ("libstdc++.so.6", "CXXABI_1.3.9"),
+        ]);
+
+        // 2.17 is newer than 2.2.5 by number, though not by spelling, and GCC_7.0.0 is
+        // only ever compared to a GCC_ baseline. CXXABI_ has none, so isn't checked.
+        let baselines = versions(&["GLIBC_2.2.5", "GCC_3.0"]);
+        let too_new: Vec<String> = elf
+            .versions_newer_than(&baselines)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            too_new,
+            [
+                "libc.so.6 needs GLIBC_2.17, newer than the GLIBC_2.2.5 baseline",
+                "libgcc_s.so.1 needs GCC_7.0.0, newer than the GCC_3.0 baseline",
+            ]
+        );
+
+        let baselines = versions(&["GLIBC_2.17", "GCC_7.0.0", "CXXABI_1.3.9"]);
+        assert_eq!(elf.versions_newer_than(&baselines), []);
+        assert_eq!(elf.versions_newer_than(&versions(&["GLIBC_17.0"])), []);
+    }
+
+    #[test]
+    fn relocation_lines() {
+        let lines = |class| {
+            let elf = parse_elf(&build_relocation_elf(class, Endianness::Little)).unwrap();
+            elf.relocation_tables
+                .iter()
+                .flat_map(|table| {
+                    let symbols = elf.symbol_tables.first();
+                    let elf = &elf;
+                    table
+                        .relocations
+                        .iter()
+                        .map(move |relocation| elf.relocation_line(relocation, symbols))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            lines(Class::Elf32),
+            [
+                "00404000  00000101 R_X86_64_64       00404010   counter",
+                "00404008  00000102 R_X86_64_PC32     00404010   counter - 4",
+                "00404018  00000008 R_X86_64_RELATIVE            401000",
+            ]
+        );
+        assert_eq!(
+            lines(Class::Elf64),
+            [
+                "000000404000  000100000001 R_X86_64_64       0000000000404010 counter",
+                "000000404008  000100000002 R_X86_64_PC32     0000000000404010 counter - 4",
+                "000000404018  000000000008 R_X86_64_RELATIVE                    401000",
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_through_gnu_hash() {
+        let mut elf = parse_elf(&build_hashed_elf()).unwrap();
+        let table = elf.gnu_hash_table.as_ref().unwrap();
+        for (idx, name) in HASHED_SYMBOLS.iter().enumerate() {
+            let (found, symbol) = elf.lookup_dynamic_symbol(name, None).unwrap();
+            assert_eq!((found, symbol.name.as_str()), (idx + 1, *name));
+        }
+
+        // The bloom filter only has the bits of the symbols that are there
+        assert!(!table.bloom_filter_matches(hash::gnu_hash(b"missing")));
+        assert!(elf.lookup_dynamic_symbol("missing", None).is_none());
+
+        // Let everything through, so the chain has to turn it away
+        elf.gnu_hash_table.as_mut().unwrap().bloom = vec![u64::MAX];
+        assert!(elf.lookup_dynamic_symbol("missing", None).is_none());
+        assert_eq!(elf.lookup_dynamic_symbol("bar", None).unwrap().0, 2);
+    }
+
+    #[test]
+    fn lookup_through_sysv_hash() {
+        let mut elf = parse_elf(&build_hashed_elf()).unwrap();
+        elf.gnu_hash_table = None;
+        let table = elf.hash_table.as_ref().unwrap();
+        assert_eq!(table.candidates("missing"), [2, 1]); // There is no filter in front
+        for (idx, name) in HASHED_SYMBOLS.iter().enumerate() {
+            let (found, symbol) = elf.lookup_dynamic_symbol(name, None).unwrap();
+            assert_eq!((found, symbol.name.as_str()), (idx + 1, *name));
+        }
+        assert!(elf.lookup_dynamic_symbol("missing", None).is_none());
+
+        // Nor a symbol that is there but undefined
+        let symbols = elf
+            .symbol_tables
+            .iter_mut()
+            .find(|table| table.section_index == 2);
+        symbols.unwrap().symbols[1].shndx = abi::SHN_UNDEF;
+        assert!(elf.lookup_dynamic_symbol(HASHED_SYMBOLS[0], None).is_none());
+    }
+
+    #[test]
+    fn bucket_histograms() {
+        let elf = parse_elf(&build_hashed_elf()).unwrap();
+        assert_eq!(elf.hash_table.as_ref().unwrap().chain_lengths(), [2]);
+        assert_eq!(
+            Elf::bucket_histogram("bucket list length", &[2]),
+            [
+                "Histogram for bucket list length (total of 1 bucket):",
+                " Length  Number     % of total  Coverage",
+                "      0  0          (  0.0%)",
+                "      1  0          (  0.0%)      0.0%",
+                "      2  1          (100.0%)    100.0%",
+            ]
+        );
+        assert_eq!(
+            Elf::bucket_histogram("`.gnu.hash' bucket list length", &[0, 1, 3, 0]),
+            [
+                "Histogram for `.gnu.hash' bucket list length (total of 4 buckets):",
+                " Length  Number     % of total  Coverage",
+                "      0  2          ( 50.0%)",
+                "      1  1          ( 25.0%)     25.0%",
+                "      2  0          (  0.0%)     25.0%",
+                "      3  1          ( 25.0%)    100.0%",
+            ]
+        );
+        assert_eq!(Elf::bucket_histogram("bucket list length", &[]).len(), 2);
+    }
+
+    #[test]
+    fn program_header_columns() {
+        let elf = |class| {
+            let segments = [Segment::new(0x401000, &[0xc3; 5])];
+            let buffer = build_elf_with(class, Endianness::Little, 0x401000, &segments, &[]);
+            parse_elf(&buffer).unwrap()
+        };
+
+        // ELF32 rows fit on one line either way
+        let elf32 = elf(Class::Elf32);
+        let expected = [
+            "Program Headers:",
+            "  Type           Offset   VirtAddr   PhysAddr   FileSiz MemSiz  Flg Align",
+            "  LOAD           0x000054 0x00401000 0x00401000 0x00005 0x00005 R E 0x1",
+        ];
+        assert_eq!(elf32.program_header_lines(false), expected);
+        assert_eq!(elf32.program_header_lines(true), expected);
+
+        let mut elf64 = elf(Class::Elf64);
+        assert_eq!(
+            elf64.program_header_lines(false),
+            [
+                "Program Headers:",
+                "  Type           Offset             VirtAddr           PhysAddr",
+                "                 FileSiz            MemSiz              Flags  Align",
+                "  LOAD           0x0000000000000078 0x0000000000401000 0x0000000000401000",
+                "                 0x0000000000000005 0x0000000000000005  R E    0x1",
+            ]
+        );
+        // Bits under PF_MASKOS aren't shown, so the column keeps its width
+        elf64.program_header_table[0].flags = SegmentFlags(abi::PF_R | abi::PF_W | 0x00100000);
+        assert_eq!(
+            elf64.program_header_lines(true),
+            [
+                "Program Headers:",
+                "  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align",
+                "  LOAD           0x000078 0x0000000000401000 0x0000000000401000 0x000005 0x000005 RW  0x1",
+            ]
+        );
     }
 }
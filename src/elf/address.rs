@@ -0,0 +1,159 @@
+//! Translating between virtual addresses and file offsets, through the PT_LOAD
+//! segments, the way the loader maps the file.
+
+use std::cmp;
+use std::fmt::{Display, Formatter};
+
+use crate::elf::program_header::{HeaderType, ProgramHeader};
+use crate::elf::Elf;
+use crate::parse::{file_range, ParseError};
+
+/// Why bytes couldn't be read at a virtual address
+#[derive(Debug)]
+pub enum AddressError {
+    /// Reading the segment's bytes out of the file failed
+    ParseError(ParseError),
+    /// No PT_LOAD segment maps the address
+    Unmapped(u64),
+}
+
+impl std::error::Error for AddressError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            AddressError::ParseError(ref err) => Some(err),
+            AddressError::Unmapped(_) => None,
+        }
+    }
+}
+
+impl Display for AddressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            AddressError::ParseError(ref err) => err.fmt(f),
+            AddressError::Unmapped(address) => {
+                write!(f, "No loadable segment maps address {address:#x}")
+            }
+        }
+    }
+}
+
+impl From<ParseError> for AddressError {
+    fn from(err: ParseError) -> Self {
+        AddressError::ParseError(err)
+    }
+}
+
+fn load_segments(program_headers: &[ProgramHeader]) -> impl Iterator<Item = &ProgramHeader> {
+    program_headers
+        .iter()
+        .filter(|ph| ph.header_type == HeaderType::Load)
+}
+
+/// File offset of the byte loaded at `address`. None if no PT_LOAD segment loads it
+/// from the file, which includes the zero-filled `.bss` part of a segment. Takes the
+/// program headers alone so the parser can use it before there's an `Elf`.
+pub fn virtual_address_to_offset(program_headers: &[ProgramHeader], address: u64) -> Option<u64> {
+    load_segments(program_headers).find_map(|ph| ph.address_to_offset(address))
+}
+
+impl Elf {
+    /// File offset of the byte loaded at `address`, as [`virtual_address_to_offset`]
+    pub fn virtual_address_to_offset(&self, address: u64) -> Option<u64> {
+        virtual_address_to_offset(&self.program_header_table, address)
+    }
+
+    /// Virtual address the byte at file offset `offset` gets loaded at. None if no
+    /// PT_LOAD segment loads it.
+    pub fn offset_to_virtual_address(&self, offset: u64) -> Option<u64> {
+        load_segments(&self.program_header_table).find_map(|ph| ph.offset_to_address(offset))
+    }
+
+    /// The `length` bytes at `address` as they'd be once loaded, with the part of each
+    /// segment past `size_in_file` read as zeros. `buffer` is the file this was parsed
+    /// from. The range may span segments, but every byte of it has to be mapped.
+    pub fn read_virtual(
+        &self,
+        buffer: &[u8],
+        address: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, AddressError> {
+        let end = address
+            .checked_add(length)
+            .ok_or(ParseError::IntegerOverflow)?;
+        let mut bytes = Vec::new();
+        let mut address = address;
+        while address < end {
+            let ph = load_segments(&self.program_header_table)
+                .find(|ph| ph.contains_address(address))
+                .ok_or(AddressError::Unmapped(address))?;
+            let start_in_segment = address - ph.virtual_address;
+            let read_length = cmp::min(end - address, ph.size_in_memory - start_in_segment);
+            let file_length = cmp::min(
+                read_length,
+                ph.size_in_file.saturating_sub(start_in_segment),
+            );
+
+            let file_offset = ph
+                .offset
+                .checked_add(start_in_segment)
+                .ok_or(ParseError::IntegerOverflow)?;
+            bytes.extend_from_slice(file_range(buffer, file_offset, file_length)?);
+            let zeros = usize::try_from(read_length - file_length).map_err(ParseError::from)?;
+            bytes.resize(bytes.len() + zeros, 0);
+            address += read_length;
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::{build_elf, Segment};
+    use crate::parse::parse_elf;
+
+    /// 4 bytes at 0x1000 running on as 4 of bss, then 2 bytes right after at 0x1008
+    fn segments() -> [Segment; 2] {
+        let mut with_bss = Segment::new(0x1000, &[1, 2, 3, 4]);
+        with_bss.size_in_memory = 8;
+        [with_bss, Segment::new(0x1008, &[5, 6])]
+    }
+
+    #[test]
+    fn bss_has_no_file_offset() {
+        let buffer = build_elf(0x1000, &segments());
+        let elf = parse_elf(&buffer).unwrap();
+        let offset = elf.virtual_address_to_offset(0x1003).unwrap();
+        assert_eq!(buffer[offset as usize], 4);
+        assert_eq!(elf.offset_to_virtual_address(offset), Some(0x1003));
+        assert_eq!(elf.virtual_address_to_offset(0x1004), None);
+
+        // But it's read as zeros
+        assert_eq!(elf.read_virtual(&buffer, 0x1002, 4).unwrap(), [3, 4, 0, 0]);
+    }
+
+    #[test]
+    fn end_of_the_last_segment_is_unmapped() {
+        let buffer = build_elf(0x1000, &segments());
+        let elf = parse_elf(&buffer).unwrap();
+        assert_eq!(elf.read_virtual(&buffer, 0x1009, 1).unwrap(), [6]);
+        assert_eq!(elf.read_virtual(&buffer, 0x100a, 0).unwrap(), []);
+        assert_eq!(elf.virtual_address_to_offset(0x100a), None);
+        assert!(matches!(
+            elf.read_virtual(&buffer, 0x1009, 2),
+            Err(AddressError::Unmapped(0x100a))
+        ));
+        assert!(matches!(
+            elf.read_virtual(&buffer, 0xfff, 2),
+            Err(AddressError::Unmapped(0xfff))
+        ));
+    }
+
+    #[test]
+    fn read_across_segments() {
+        let buffer = build_elf(0x1000, &segments());
+        let elf = parse_elf(&buffer).unwrap();
+        let bytes = elf.read_virtual(&buffer, 0x1003, 7).unwrap();
+        assert_eq!(bytes, [4, 0, 0, 0, 0, 5, 6]);
+    }
+}
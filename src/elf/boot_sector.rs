@@ -0,0 +1,283 @@
+//! Checking that an ELF file linked for a BIOS boot sector would actually boot. The
+//! BIOS loads the disk's first 512 bytes at 0x7C00, and only jumps to them if they end
+//! in the 0x55 0xAA signature, so everything has to fit in the 510 bytes before it.
+
+use std::fmt::{Display, Formatter};
+
+use crate::elf::image::{ImageError, LoadAddress};
+use crate::elf::program_header::HeaderType;
+use crate::elf::Elf;
+
+/// Where the BIOS loads the boot sector
+pub const BOOT_SECTOR_ADDRESS: u64 = 0x7C00;
+pub const BOOT_SECTOR_SIZE: u64 = 512;
+/// Offset of the signature, which is also how many bytes are left for code and data
+pub const BOOT_SIGNATURE_OFFSET: u64 = 510;
+pub const BOOT_SIGNATURE: [u8; 2] = [0x55, 0xAA];
+
+/// A reason the BIOS wouldn't load or run the image as linked
+#[derive(Debug, Eq, PartialEq)]
+pub enum BootSectorProblem {
+    /// The image runs this many bytes from 0x7C00, past the 510 there's room for
+    ImageTooLarge(u64),
+    /// The entry point isn't in the 510 bytes from 0x7C00
+    EntryOutside(u64),
+    /// The two bytes at offset 510 of the image aren't 0x55 0xAA
+    MissingSignature([u8; 2]),
+    /// A PT_LOAD segment, by program header index, needs memory outside the
+    /// sector, which the BIOS won't have loaded or cleared
+    SegmentOutsideSector(usize),
+}
+
+impl Display for BootSectorProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            BootSectorProblem::ImageTooLarge(length) => write!(
+                f,
+                "Image is {length} bytes, more than the {BOOT_SIGNATURE_OFFSET} that fit before the signature"
+            ),
+            BootSectorProblem::EntryOutside(entry) => {
+                write!(f, "Entry point {entry:#x} is outside the boot sector")
+            }
+            BootSectorProblem::MissingSignature([first, second]) => write!(
+                f,
+                "Expected the 0x55 0xaa signature at offset {BOOT_SIGNATURE_OFFSET}, found {first:#04x} {second:#04x}"
+            ),
+            BootSectorProblem::SegmentOutsideSector(index) => write!(
+                f,
+                "Segment {index:02} needs memory outside {BOOT_SECTOR_ADDRESS:#x}..{:#x}",
+                BOOT_SECTOR_ADDRESS + BOOT_SECTOR_SIZE
+            ),
+        }
+    }
+}
+
+impl Elf {
+    /// The first sector as the BIOS would load it: the segment bytes from 0x7C00, with
+    /// zeros anywhere they leave empty. Bytes past the sector are left out.
+    pub fn boot_sector_image(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<[u8; BOOT_SECTOR_SIZE as usize], ImageError> {
+        let (image, _) = self.boot_sector_image_and_length(buffer, load_address)?;
+        Ok(image)
+    }
+
+    /// The first sector, and how many bytes from 0x7C00 the segments' file bytes run,
+    /// which may be past the sector
+    fn boot_sector_image_and_length(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<([u8; BOOT_SECTOR_SIZE as usize], u64), ImageError> {
+        let mut image = [0; BOOT_SECTOR_SIZE as usize];
+        let sector_end = BOOT_SECTOR_ADDRESS + BOOT_SECTOR_SIZE;
+        let segments = self.loaded_segments(buffer, load_address)?;
+        for segment in segments.iter() {
+            let start = segment.address.clamp(BOOT_SECTOR_ADDRESS, sector_end);
+            let end = segment.end().clamp(BOOT_SECTOR_ADDRESS, sector_end);
+            if start == end {
+                continue;
+            }
+            let data =
+                &segment.data[(start - segment.address) as usize..][..(end - start) as usize];
+            image[(start - BOOT_SECTOR_ADDRESS) as usize..][..data.len()].copy_from_slice(data);
+        }
+        let image_end = segments
+            .iter()
+            .map(|segment| segment.end())
+            .max()
+            .unwrap_or(BOOT_SECTOR_ADDRESS);
+        Ok((image, image_end.saturating_sub(BOOT_SECTOR_ADDRESS)))
+    }
+
+    /// The first sector with the 0x55 0xAA signature written at offset 510.
+    /// `ImageError::TooLarge` if the image already has other bytes there.
+    pub fn boot_sector_with_signature(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<[u8; BOOT_SECTOR_SIZE as usize], ImageError> {
+        let (mut image, image_length) = self.boot_sector_image_and_length(buffer, load_address)?;
+        let signature_offset = BOOT_SIGNATURE_OFFSET as usize;
+        if image_length > BOOT_SECTOR_SIZE
+            || (image_length > BOOT_SIGNATURE_OFFSET && image[signature_offset..] != BOOT_SIGNATURE)
+        {
+            return Err(ImageError::TooLarge(image_length));
+        }
+        image[signature_offset..].copy_from_slice(&BOOT_SIGNATURE);
+        Ok(image)
+    }
+
+    /// Everything that would keep this from booting from a disk's first sector. Empty
+    /// if it's fine.
+    pub fn check_boot_sector(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<Vec<BootSectorProblem>, ImageError> {
+        let mut problems = Vec::new();
+        let sector_end = BOOT_SECTOR_ADDRESS + BOOT_SECTOR_SIZE;
+        let signature_offset = BOOT_SIGNATURE_OFFSET as usize;
+
+        let (image, image_length) = self.boot_sector_image_and_length(buffer, load_address)?;
+        let found_signature = [image[signature_offset], image[signature_offset + 1]];
+        let has_signature = found_signature == BOOT_SIGNATURE;
+
+        // The image may end in the signature itself, if the linker script put it there
+        let room = match has_signature {
+            true => BOOT_SECTOR_SIZE,
+            false => BOOT_SIGNATURE_OFFSET,
+        };
+        if image_length > room {
+            problems.push(BootSectorProblem::ImageTooLarge(image_length));
+        }
+
+        let entry = self.header.entry;
+        if !(BOOT_SECTOR_ADDRESS..BOOT_SECTOR_ADDRESS + BOOT_SIGNATURE_OFFSET).contains(&entry) {
+            problems.push(BootSectorProblem::EntryOutside(entry));
+        }
+
+        if !has_signature {
+            problems.push(BootSectorProblem::MissingSignature(found_signature));
+        }
+
+        for (index, ph) in self.program_header_table.iter().enumerate() {
+            if ph.header_type != HeaderType::Load || ph.size_in_memory == 0 {
+                continue;
+            }
+            let start = load_address.of(ph);
+            let end = start.saturating_add(ph.size_in_memory);
+            if start < BOOT_SECTOR_ADDRESS || end > sector_end {
+                problems.push(BootSectorProblem::SegmentOutsideSector(index));
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::{build_elf, Segment};
+    use crate::parse::parse_elf;
+
+    fn check(entry: u64, segments: &[Segment]) -> Vec<BootSectorProblem> {
+        let buffer = build_elf(entry, segments);
+        let elf = parse_elf(&buffer).unwrap();
+        elf.check_boot_sector(&buffer, LoadAddress::Physical)
+            .unwrap()
+    }
+
+    fn with_signature(entry: u64, segments: &[Segment]) -> Result<[u8; 512], ImageError> {
+        let buffer = build_elf(entry, segments);
+        let elf = parse_elf(&buffer).unwrap();
+        elf.boot_sector_with_signature(&buffer, LoadAddress::Physical)
+    }
+
+    /// `code` followed by zeros, up to and including the signature
+    fn signed_sector(code: &[u8]) -> Vec<u8> {
+        let mut sector = code.to_vec();
+        sector.resize(BOOT_SIGNATURE_OFFSET as usize, 0);
+        sector.extend(BOOT_SIGNATURE);
+        sector
+    }
+
+    #[test]
+    fn signed_sector_has_no_problems() {
+        let segments = [Segment::new(
+            BOOT_SECTOR_ADDRESS,
+            &signed_sector(&[0xeb, 0xfe]),
+        )];
+        assert_eq!(check(BOOT_SECTOR_ADDRESS, &segments), []);
+    }
+
+    #[test]
+    fn entry_outside_the_sector() {
+        let segments = [Segment::new(
+            BOOT_SECTOR_ADDRESS,
+            &signed_sector(&[0xeb, 0xfe]),
+        )];
+        assert_eq!(
+            check(0x7e00, &segments),
+            [BootSectorProblem::EntryOutside(0x7e00)]
+        );
+        // The signature's bytes aren't code
+        let entry = BOOT_SECTOR_ADDRESS + BOOT_SIGNATURE_OFFSET;
+        assert_eq!(
+            check(entry, &segments),
+            [BootSectorProblem::EntryOutside(entry)]
+        );
+    }
+
+    #[test]
+    fn missing_signature() {
+        let segments = [Segment::new(BOOT_SECTOR_ADDRESS, &[0xeb, 0xfe])];
+        assert_eq!(
+            check(BOOT_SECTOR_ADDRESS, &segments),
+            [BootSectorProblem::MissingSignature([0, 0])]
+        );
+    }
+
+    #[test]
+    fn image_too_large() {
+        let mut code = signed_sector(&[0xeb, 0xfe]);
+        code.push(0x90);
+        let segments = [Segment::new(BOOT_SECTOR_ADDRESS, &code)];
+        assert_eq!(
+            check(BOOT_SECTOR_ADDRESS, &segments),
+            [
+                BootSectorProblem::ImageTooLarge(513),
+                BootSectorProblem::SegmentOutsideSector(0),
+            ]
+        );
+
+        // Without a signature, only 510 bytes fit
+        let segments = [Segment::new(BOOT_SECTOR_ADDRESS, &[0x90; 511])];
+        assert_eq!(
+            check(BOOT_SECTOR_ADDRESS, &segments),
+            [
+                BootSectorProblem::ImageTooLarge(511),
+                BootSectorProblem::MissingSignature([0x90, 0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn image_is_cut_off_at_the_end_of_the_sector() {
+        let buffer = build_elf(
+            BOOT_SECTOR_ADDRESS,
+            &[Segment::new(0x7d00, &[0x90; 0x1000])],
+        );
+        let elf = parse_elf(&buffer).unwrap();
+        let image = elf
+            .boot_sector_image(&buffer, LoadAddress::Physical)
+            .unwrap();
+        assert_eq!(image[..0x100], [0; 0x100]);
+        assert_eq!(image[0x100..], [0x90; 0x100]);
+    }
+
+    #[test]
+    fn signature_is_added() {
+        let segments = [Segment::new(BOOT_SECTOR_ADDRESS, &[0xeb, 0xfe])];
+        let sector = with_signature(BOOT_SECTOR_ADDRESS, &segments).unwrap();
+        assert_eq!(sector.to_vec(), signed_sector(&[0xeb, 0xfe]));
+
+        // Already there is fine too
+        let segments = [Segment::new(
+            BOOT_SECTOR_ADDRESS,
+            &signed_sector(&[0xeb, 0xfe]),
+        )];
+        let sector = with_signature(BOOT_SECTOR_ADDRESS, &segments).unwrap();
+        assert_eq!(sector.to_vec(), signed_sector(&[0xeb, 0xfe]));
+    }
+
+    #[test]
+    fn no_room_for_the_signature() {
+        let segments = [Segment::new(BOOT_SECTOR_ADDRESS, &[0x90; 511])];
+        let result = with_signature(BOOT_SECTOR_ADDRESS, &segments);
+        assert!(matches!(result, Err(ImageError::TooLarge(511))));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::abi;
+use crate::to_str;
+
+/// One entry of the dynamic array
+pub struct DynamicEntry {
+    pub tag: i64,   // d_tag, Sword in ELF32
+    pub value: u64, // d_val or d_ptr
+    /// Resolved through DT_STRTAB, for the tags whose value is a string table offset
+    pub string: Option<String>,
+}
+
+impl DynamicEntry {
+    /// True for the tags whose value is an offset into the DT_STRTAB string table
+    pub fn has_string_value(&self) -> bool {
+        matches!(
+            self.tag,
+            abi::DT_NEEDED | abi::DT_SONAME | abi::DT_RPATH | abi::DT_RUNPATH
+        )
+    }
+
+    /// Tag name as GNU readelf shows it, e.g. NEEDED
+    pub fn tag_name(&self) -> String {
+        match to_str::d_tag_to_str(self.tag) {
+            Some(name) => name.trim_start_matches("DT_").to_string(),
+            None => format!("{:#x}", self.tag),
+        }
+    }
+}
+
+/// The dynamic array, from PT_DYNAMIC or the .dynamic section
+pub struct DynamicSection {
+    pub offset: u64,
+    pub entries: Vec<DynamicEntry>,
+}
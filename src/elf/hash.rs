@@ -0,0 +1,252 @@
+use crate::parse::ParseError;
+
+/// The SysV symbol hash function from the gABI
+pub fn elf_hash(name: &[u8]) -> u32 {
+    let mut hash: u32 = 0;
+    for &byte in name {
+        hash = (hash << 4).wrapping_add(byte as u32);
+        let high = hash & 0xf0000000;
+        if high != 0 {
+            hash ^= high >> 24;
+        }
+        hash &= !high;
+    }
+    hash
+}
+
+/// The GNU symbol hash function, which is Bernstein's djb2
+pub fn gnu_hash(name: &[u8]) -> u32 {
+    name.iter().fold(5381u32, |hash, &byte| {
+        hash.wrapping_mul(33).wrapping_add(byte as u32)
+    })
+}
+
+/// The SHT_HASH section. Each bucket holds the first symbol index of a chain, and
+/// `chains[index]` the next index, until STN_UNDEF.
+pub struct HashTable {
+    pub section_index: usize,
+    pub buckets: Vec<u32>,
+    pub chains: Vec<u32>, // One per dynamic symbol
+}
+
+impl HashTable {
+    /// Symbol indexes in the chain `name` falls into, in the order ld.so tries them
+    pub fn candidates(&self, name: &str) -> Vec<u32> {
+        if self.buckets.is_empty() {
+            return Vec::new();
+        }
+        let hash = elf_hash(name.as_bytes());
+        let mut result = Vec::new();
+        let mut index = self.buckets[hash as usize % self.buckets.len()];
+        // A chain can't be longer than the symbol table, unless it loops
+        while index != 0 && result.len() < self.chains.len() {
+            result.push(index);
+            index = *self.chains.get(index as usize).unwrap_or(&0);
+        }
+        result
+    }
+
+    /// Number of symbols in each bucket, the way readelf counts them for `-I`
+    pub fn chain_lengths(&self) -> Vec<usize> {
+        let chain_count = self.chains.len() as u32;
+        self.buckets
+            .iter()
+            .map(|&bucket| {
+                let mut length = 0;
+                let mut index = bucket;
+                // A chain can't be longer than the symbol table, unless it loops
+                while index > 0 && index < chain_count && length < self.chains.len() {
+                    length += 1;
+                    index = self.chains[index as usize];
+                }
+                length
+            })
+            .collect()
+    }
+}
+
+/// The SHT_GNU_HASH section. Symbols from `symbol_offset` on are sorted by bucket, so each
+/// bucket holds the first symbol index of a run, and the chain holds each run's hashes with
+/// the low bit marking its end. A bloom filter in front rejects most misses early.
+pub struct GnuHashTable {
+    pub section_index: usize,
+    pub symbol_offset: u32, // Index of the first symbol the table covers
+    pub bloom_shift: u32,
+    pub bloom_word_bits: u32, // 32 in ELF32, 64 in ELF64
+    pub bloom: Vec<u64>,
+    pub buckets: Vec<u32>,
+    pub chain: Vec<u32>, // One hash per symbol from `symbol_offset` on
+}
+
+impl GnuHashTable {
+    /// Whether the bloom filter lets `hash` through. False means the symbol is
+    /// certainly not in the table.
+    pub fn bloom_filter_matches(&self, hash: u32) -> bool {
+        if self.bloom.is_empty() {
+            return false;
+        }
+        let bits = self.bloom_word_bits;
+        // ld.so masks rather than takes the remainder, as the word count is a power of two
+        let word = self.bloom[(hash / bits) as usize & (self.bloom.len() - 1)];
+        let bit1 = hash % bits;
+        let bit2 = hash.checked_shr(self.bloom_shift).unwrap_or(0) % bits;
+        (word >> bit1) & (word >> bit2) & 1 != 0
+    }
+
+    /// Symbol indexes whose hash matches `name`, in the order ld.so tries them. Empty if
+    /// the bloom filter rejects the name.
+    pub fn candidates(&self, name: &str) -> Vec<u32> {
+        let hash = gnu_hash(name.as_bytes());
+        if self.buckets.is_empty() || !self.bloom_filter_matches(hash) {
+            return Vec::new();
+        }
+        let bucket = self.buckets[hash as usize % self.buckets.len()];
+        if bucket < self.symbol_offset {
+            return Vec::new();
+        }
+
+        let mut result = Vec::new();
+        let start = (bucket - self.symbol_offset) as usize;
+        for (offset, &chain_hash) in self.chain.iter().enumerate().skip(start) {
+            // The low bit is the end marker, so only the upper 31 bits are compared
+            if (chain_hash ^ hash) >> 1 == 0 {
+                result.push(self.symbol_offset.wrapping_add(offset as u32));
+            }
+            if chain_hash & 1 != 0 {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Every non-empty bucket has to start at or after `symbol_offset`, as the chain
+    /// only covers the symbols from there on
+    pub fn check_buckets(&self) -> Result<(), ParseError> {
+        match self
+            .buckets
+            .iter()
+            .find(|&&bucket| bucket != 0 && bucket < self.symbol_offset)
+        {
+            Some(&bucket) => Err(ParseError::BadHashBucket((bucket, self.symbol_offset))),
+            None => Ok(()),
+        }
+    }
+
+    /// Number of symbols in each bucket, the way readelf counts them for `-I`
+    pub fn chain_lengths(&self) -> Vec<usize> {
+        self.buckets
+            .iter()
+            .map(|&bucket| {
+                if bucket == 0 {
+                    return 0;
+                }
+                // `check_buckets` rules out buckets below the offset when parsing
+                let Some(start) = bucket.checked_sub(self.symbol_offset) else {
+                    return 0;
+                };
+                let run = self.chain.get(start as usize..).unwrap_or_default();
+                // The entry with the end marker counts too, if the table doesn't run out
+                // before it
+                match run.iter().position(|&chain_hash| chain_hash & 1 != 0) {
+                    Some(end) => end + 1,
+                    None => run.len(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gnu_hash_table(buckets: &[u32]) -> GnuHashTable {
+        GnuHashTable {
+            section_index: 0,
+            symbol_offset: 4,
+            bloom_shift: 0,
+            bloom_word_bits: 64,
+            bloom: vec![u64::MAX],
+            buckets: buckets.to_vec(),
+            // Symbols 4 and 5 in the first bucket, 6 in the second
+            chain: vec![0, 1, 1],
+        }
+    }
+
+    #[test]
+    fn gnu_chain_lengths() {
+        let table = gnu_hash_table(&[4, 0, 6]);
+        assert!(table.check_buckets().is_ok());
+        assert_eq!(table.chain_lengths(), [2, 0, 1]);
+    }
+
+    #[test]
+    fn gnu_bucket_below_symbol_offset() {
+        let table = gnu_hash_table(&[4, 3]);
+        assert!(matches!(
+            table.check_buckets(),
+            Err(ParseError::BadHashBucket((3, 4)))
+        ));
+        assert_eq!(table.chain_lengths(), [2, 0]);
+    }
+
+    #[test]
+    fn gnu_chain_running_off_the_table() {
+        let mut table = gnu_hash_table(&[4, 6, 9]);
+        // The second bucket runs off the end without a marker, and the third starts past it
+        table.chain = vec![0, 1, 0, 0];
+        assert_eq!(table.chain_lengths(), [2, 2, 0]);
+    }
+
+    #[test]
+    fn bloom_filter() {
+        let mut table = gnu_hash_table(&[4]);
+        table.bloom_shift = 6;
+        table.bloom = vec![1 << 1];
+        assert!(table.bloom_filter_matches(0x41)); // Bits 1 and 0x41 >> 6
+        assert!(!table.bloom_filter_matches(0x42)); // Bit 2 isn't set
+        table.bloom = vec![0b11 << 32];
+        assert!(!table.bloom_filter_matches(0x41)); // Only one of the two bits
+
+        // ELF32 words hold 32 bits, and the word is picked by the hash
+        table.bloom_word_bits = 32;
+        table.bloom = vec![0, 0b11];
+        assert!(table.bloom_filter_matches(33));
+        assert!(!table.bloom_filter_matches(1));
+
+        table.bloom.clear();
+        assert!(!table.bloom_filter_matches(0x41));
+    }
+
+    #[test]
+    fn gnu_candidates_stop_at_the_end_marker() {
+        let (foo, bar) = (gnu_hash(b"foo"), gnu_hash(b"bar"));
+        let mut table = gnu_hash_table(&[4]);
+        // Past the marker, symbol 8 hashes like `foo` but belongs to another bucket
+        table.chain = vec![foo & !1, bar & !1, foo | 1, foo & !1];
+        assert_eq!(table.candidates("foo"), [4, 6]);
+        assert_eq!(table.candidates("bar"), [5]);
+        assert!(table.candidates("baz").is_empty());
+
+        table.buckets = vec![0];
+        assert!(table.candidates("foo").is_empty());
+    }
+
+    #[test]
+    fn sysv_candidates() {
+        let mut table = HashTable {
+            section_index: 0,
+            buckets: vec![3],
+            chains: vec![0, 0, 1, 2],
+        };
+        assert_eq!(table.candidates("foo"), [3, 2, 1]);
+        assert_eq!(table.chain_lengths(), [3]);
+
+        // A looping chain stops after as many symbols as there are
+        table.chains = vec![0, 0, 3, 2];
+        assert_eq!(table.candidates("foo"), [3, 2, 3, 2]);
+
+        table.buckets.clear();
+        assert!(table.candidates("foo").is_empty());
+    }
+}
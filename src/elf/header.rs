@@ -1,48 +1,167 @@
 use std::cmp;
 use std::fmt::{Debug, Display, Formatter};
 
+use crate::abi;
 use crate::to_str;
 
+/// File class from `e_ident[EI_CLASS]`, which decides the width of addresses and offsets
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Class {
+    Elf32,
+    Elf64,
+}
+
+/// On-disk sizes of the fixed-size structures, which depend on the class
+impl Class {
+    pub fn elf_header_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 52,
+            Class::Elf64 => 64,
+        }
+    }
+
+    pub fn program_header_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 32,
+            Class::Elf64 => 56,
+        }
+    }
+
+    pub fn section_header_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 40,
+            Class::Elf64 => 64,
+        }
+    }
+
+    pub fn symbol_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 16,
+            Class::Elf64 => 24,
+        }
+    }
+
+    /// An Elf_Rel entry
+    pub fn relocation_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 8,
+            Class::Elf64 => 16,
+        }
+    }
+
+    /// An Elf_Rela entry
+    pub fn relocation_with_addend_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 12,
+            Class::Elf64 => 24,
+        }
+    }
+
+    pub fn dynamic_entry_size(&self) -> usize {
+        match self {
+            Class::Elf32 => 8,
+            Class::Elf64 => 16,
+        }
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let class = match self {
+            Class::Elf32 => "ELF32",
+            Class::Elf64 => "ELF64",
+        };
+        write!(f, "{class}")
+    }
+}
+
+/// Data encoding from `e_ident[EI_DATA]`, which decides the byte order of every multi-byte field
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let human_str = match self {
+            Endianness::Little => "2's complement, little endian",
+            Endianness::Big => "2's complement, big endian",
+        };
+        write!(f, "{human_str}")
+    }
+}
+
 pub struct OsAbi(pub u8);
 
 impl Debug for OsAbi {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let &OsAbi(value) = self;
-        let value = to_str::e_osabi_to_str(value).ok_or(std::fmt::Error)?;
-        write!(f, "{value}")
+        write!(f, "{}", to_str::e_osabi_to_string(value))
     }
 }
 
 impl Display for OsAbi {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let &OsAbi(value) = self;
-        let value = to_str::e_osabi_to_human_string(value).ok_or(std::fmt::Error)?;
-        write!(f, "{value}")
+        match to_str::e_osabi_to_human_string(value) {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "<unknown: {value:x}>"),
+        }
     }
 }
 
+/// Object file type from `e_type`. The OS- and processor-specific ranges are kept as
+/// raw values, since what they mean depends on the ABI and `e_machine`.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 pub enum FileType {
     None,
-    // Unknown.
-    Rel,
-    // Relocatable file.
-    Exec,
-    // Executable file.
-    Dyn,
-    // Shared object.
-    Core, // Core file -- for core dumps
-          // I don't know what the os/processor specific reserved ones are for.
+    Rel,                    // Relocatable file
+    Exec,                   // Executable file
+    Dyn,                    // Shared object
+    Core,                   // Core dump
+    OsSpecific(u16),        // ET_LOOS..=ET_HIOS
+    ProcessorSpecific(u16), // ET_LOPROC..=ET_HIPROC
+}
+
+impl FileType {
+    /// The `e_type` value this was decoded from
+    pub fn value(&self) -> u16 {
+        match *self {
+            FileType::None => abi::ET_NONE,
+            FileType::Rel => abi::ET_REL,
+            FileType::Exec => abi::ET_EXEC,
+            FileType::Dyn => abi::ET_DYN,
+            FileType::Core => abi::ET_CORE,
+            FileType::OsSpecific(value) | FileType::ProcessorSpecific(value) => value,
+        }
+    }
+
+    /// Short name, e.g. "EXEC", with the reserved ranges named like segment types are
+    pub fn name(&self) -> String {
+        match *self {
+            FileType::OsSpecific(value) => format!("LOOS+{:#x}", value - abi::ET_LOOS),
+            FileType::ProcessorSpecific(value) => {
+                format!("LOPROC+{:#x}", value - abi::ET_LOPROC)
+            }
+            file_type => format!("{file_type:?}").to_uppercase(),
+        }
+    }
 }
 
+/// As GNU readelf shows it, e.g. "EXEC (Executable file)"
 impl Display for FileType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let human_str = match self {
-            FileType::None => "No file type",
-            FileType::Rel => "Relocatable file",
-            FileType::Exec => "Executable file",
-            FileType::Dyn => "Shared object file",
-            FileType::Core => "Core file",
+            FileType::None => "NONE (None)",
+            FileType::Rel => "REL (Relocatable file)",
+            FileType::Exec => "EXEC (Executable file)",
+            FileType::Dyn => "DYN (Shared object file)",
+            FileType::Core => "CORE (Core file)",
+            FileType::OsSpecific(value) => return write!(f, "OS Specific: ({value:x})"),
+            FileType::ProcessorSpecific(value) => {
+                return write!(f, "Processor Specific: ({value:x})");
+            }
         };
         write!(f, "{human_str}")
     }
@@ -53,59 +172,103 @@ pub struct Machine(pub u16);
 impl Debug for Machine {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let &Machine(value) = self;
-        let value = to_str::e_machine_to_str(value).ok_or(std::fmt::Error)?;
-        write!(f, "{value}")
+        write!(f, "{}", to_str::e_machine_to_string(value))
     }
 }
 
 impl Display for Machine {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let &Machine(value) = self;
-        let value = to_str::e_machine_to_human_str(value).ok_or(std::fmt::Error)?;
-        write!(f, "{value}")
+        match to_str::e_machine_to_human_str(value) {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "<unknown>: {value:#x}"),
+        }
     }
 }
 
 /// Header at the start of the ELF file
 #[derive(Debug)]
 pub struct ElfHeader {
-    // Magic number not necessary
+    pub ident: [u8; abi::EI_NIDENT], // e_ident as stored, magic and padding included
+    pub class: Class,
+    pub endianness: Endianness,
     pub os_abi: OsAbi,
     pub abi_version: u8,
     pub file_type: FileType,
     pub machine: Machine,
-    pub entry: u32,
-    pub program_header_offset: usize,     // u32
-    pub section_header_offset: usize,     // u32
+    pub version: u32, // e_version
+    pub entry: u64,
+    pub program_header_offset: usize,     // Elf_Off
+    pub section_header_offset: usize,     // Elf_Off
+    pub flags: u32,                       // e_flags, interpreted per e_machine
     pub elf_header_size: usize,           // u16
     pub program_header_entry_size: usize, // u16
-    pub program_header_entries: usize,    // u16
+    pub program_header_entries: usize,    // From section 0's sh_info if e_phnum is PN_XNUM
     pub section_header_entry_size: usize, // u16
-    pub section_header_entries: usize,    // u16
-    pub string_table_index: usize,        // u16
+    pub section_header_entries: usize,    // From section 0's sh_size if e_shnum is 0
+    pub string_table_index: usize,        // From section 0's sh_link if e_shstrndx is SHN_XINDEX
+    /// `e_phnum`, `e_shnum` and `e_shstrndx` as stored, which are escapes when the
+    /// real values don't fit
+    pub stored_program_header_entries: u16,
+    pub stored_section_header_entries: u16,
+    pub stored_string_table_index: u16,
+    pub is_pie: bool, // DYN with DF_1_PIE set in DT_FLAGS_1
+}
+
+impl ElfHeader {
+    /// The file type as GNU readelf shows it, which tells position-independent
+    /// executables apart from shared objects
+    pub fn file_type_description(&self) -> String {
+        match self.is_pie {
+            true => "DYN (Position-Independent Executable file)".to_string(),
+            false => self.file_type.to_string(),
+        }
+    }
+}
+
+/// A count as stored, followed by the real one from section 0 when the stored one is an escape
+fn extended(stored: u16, real: usize) -> String {
+    if stored as usize == real {
+        stored.to_string()
+    } else {
+        format!("{stored} ({real})")
+    }
 }
 
 impl Display for ElfHeader {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ident_version = self.ident[abi::EI_VERSION];
+        let ident_version = match ident_version {
+            abi::EV_CURRENT => format!("{ident_version} (current)"),
+            abi::EV_NONE => ident_version.to_string(),
+            _ => format!("{ident_version} <unknown>"),
+        };
         let rows: Vec<(&str, String)> = vec![
-            ("Class", "ELF32".to_string()),
-            ("Data", "2's complement, little endian".to_string()),
-            ("Version", "1 (current)".to_string()),
+            ("Class", self.class.to_string()),
+            ("Data", self.endianness.to_string()),
+            ("Version", ident_version),
             ("OS/ABI", format!("{}", self.os_abi)),
             ("ABI Version", self.abi_version.to_string()),
-            ("Type", format!("{0:?} ({0})", self.file_type)),
+            ("Type", self.file_type_description()),
             ("Machine", format!("{}", self.machine)),
-            ("Version", "0x1".to_string()),
-            ("Entry point address", self.entry.to_string()),
+            ("Version", format!("{:#x}", self.version)),
+            ("Entry point address", format!("{:#x}", self.entry)),
             (
                 "Start of program headers",
                 format!("{} (bytes into file)", self.program_header_offset),
             ),
             (
                 "Start of section headers",
-                format!("{} (bytes into file", self.section_header_offset),
+                format!("{} (bytes into file)", self.section_header_offset),
+            ),
+            (
+                "Flags",
+                format!(
+                    "{:#x}{}",
+                    self.flags,
+                    to_str::e_flags_to_string(self.machine.0, self.flags)
+                ),
             ),
-            ("Flags", "0x0".to_string()),
             (
                 "Size of this header",
                 format!("{} (bytes)", self.elf_header_size),
@@ -116,7 +279,10 @@ impl Display for ElfHeader {
             ),
             (
                 "Number of program headers",
-                self.program_header_entries.to_string(),
+                extended(
+                    self.stored_program_header_entries,
+                    self.program_header_entries,
+                ),
             ),
             (
                 "Size of section headers",
@@ -124,17 +290,21 @@ impl Display for ElfHeader {
             ),
             (
                 "Number of section headers",
-                self.section_header_entries.to_string(),
+                extended(
+                    self.stored_section_header_entries,
+                    self.section_header_entries,
+                ),
             ),
         ];
 
         let longest_field_length = "  Section header string table index: ".len() - 3; // Trust me bro
 
         writeln!(f, "ELF Header:")?;
-        writeln!(
-            f,
-            "  Magic:   7f 45 4c 46 01 01 01 00 00 00 00 00 00 00 00 00"
-        )?;
+        write!(f, "  Magic:   ")?;
+        for byte in self.ident {
+            write!(f, "{byte:02x} ")?;
+        }
+        writeln!(f)?;
         for (field, value) in rows {
             let padding = cmp::max(longest_field_length - field.len(), 0);
             let padding = " ".repeat(padding);
@@ -143,9 +313,87 @@ impl Display for ElfHeader {
         write!(
             f,
             "  Section header string table index: {}",
-            self.string_table_index
+            extended(self.stored_string_table_index, self.string_table_index)
         )?;
+        if self.string_table_index != 0 && self.string_table_index >= self.section_header_entries {
+            write!(f, " <corrupt: out of range>")?;
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::build_elf;
+    use crate::parse::parse_elf;
+
+    /// `build_elf`'s header with `e_type` replaced
+    fn header_with_type(file_type: u16) -> ElfHeader {
+        let mut buffer = build_elf(0x401000, &[]);
+        buffer[16..18].copy_from_slice(&file_type.to_le_bytes());
+        parse_elf(&buffer).unwrap().header
+    }
+
+    #[test]
+    fn header_display() {
+        let header = header_with_type(abi::ET_EXEC);
+        let expected = [
+            "ELF Header:",
+            "  Magic:   7f 45 4c 46 02 01 01 00 00 00 00 00 00 00 00 00 ",
+            "  Class:                             ELF64",
+            "  Data:                              2's complement, little endian",
+            "  Version:                           1 (current)",
+            "  OS/ABI:                            UNIX - System V",
+            "  ABI Version:                       0",
+            "  Type:                              EXEC (Executable file)",
+            "  Machine:                           AMD x86-64 architecture",
+            "  Version:                           0x1",
+            "  Entry point address:               0x401000",
+            "  Start of program headers:          64 (bytes into file)",
+            "  Start of section headers:          0 (bytes into file)",
+            "  Flags:                             0x0",
+            "  Size of this header:               64 (bytes)",
+            "  Size of program headers:           56 (bytes)",
+            "  Number of program headers:         0",
+            "  Size of section headers:           64 (bytes)",
+            "  Number of section headers:         0",
+            "  Section header string table index: 0",
+        ];
+        assert_eq!(header.to_string(), expected.join("\n"));
+    }
+
+    #[test]
+    fn machine_flags_in_the_header() {
+        let mut header = header_with_type(abi::ET_DYN);
+        header.machine = Machine(abi::EM_ARM);
+        header.flags = abi::EF_ARM_EABI_VER5 | abi::EF_ARM_ABI_FLOAT_HARD;
+        let text = header.to_string();
+        assert!(text.contains(
+            "\n  Flags:                             0x5000400, Version5 EABI, hard-float ABI\n"
+        ));
+        assert!(text.contains(
+            "\n  Machine:                           ARM 32-bit architecture (AARCH32)\n"
+        ));
+    }
+
+    #[test]
+    fn reserved_file_types() {
+        let header = header_with_type(0xfe01);
+        assert_eq!(header.file_type, FileType::OsSpecific(0xfe01));
+        assert!(header
+            .to_string()
+            .contains("\n  Type:                              OS Specific: (fe01)\n"));
+
+        let processor = header_with_type(abi::ET_HIPROC).file_type;
+        assert_eq!(processor, FileType::ProcessorSpecific(0xffff));
+        assert_eq!(processor.to_string(), "Processor Specific: (ffff)");
+        assert_eq!(processor.name(), "LOPROC+0xff");
+        assert_eq!(FileType::OsSpecific(abi::ET_LOOS).name(), "LOOS+0x0");
+        assert_eq!(
+            (FileType::Core.value(), FileType::Core.name().as_str()),
+            (abi::ET_CORE, "CORE")
+        );
+    }
+}
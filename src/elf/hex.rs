@@ -0,0 +1,240 @@
+//! Writing the PT_LOAD segments as Intel HEX or Motorola S-records, the text formats
+//! flash programmers take. Both give each record a checksum, and end with one holding
+//! the entry point. Lines end in CRLF, as objcopy writes them.
+
+use std::fmt::Write;
+
+use crate::elf::image::{ImageError, LoadAddress, LoadedSegment};
+use crate::elf::Elf;
+
+/// Bytes of data per record, the same as objcopy
+const RECORD_DATA_LENGTH: usize = 16;
+
+/// One `:LLAAAATT<data>CC` line. The checksum makes the record's bytes sum to zero.
+fn write_intel_hex_record(out: &mut String, record_type: u8, address: u16, data: &[u8]) {
+    let [address_high, address_low] = address.to_be_bytes();
+    let header = [data.len() as u8, address_high, address_low, record_type];
+    let sum = header
+        .iter()
+        .chain(data)
+        .fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+
+    out.push(':');
+    for byte in header.iter().chain(data) {
+        let _ = write!(out, "{byte:02X}");
+    }
+    let _ = write!(out, "{:02X}\r\n", sum.wrapping_neg());
+}
+
+/// One `S<type><count><address><data><checksum>` line, with the address `address_length`
+/// bytes wide. The checksum is the ones' complement of the sum of the other bytes.
+fn write_srec_record(
+    out: &mut String,
+    record_type: u8,
+    address: u32,
+    address_length: usize,
+    data: &[u8],
+) {
+    let address = &address.to_be_bytes()[4 - address_length..];
+    let count = (address_length + data.len() + 1) as u8;
+    let sum = address
+        .iter()
+        .chain(data)
+        .fold(count, |sum, &byte| sum.wrapping_add(byte));
+
+    let _ = write!(out, "S{record_type}{count:02X}");
+    for byte in address.iter().chain(data) {
+        let _ = write!(out, "{byte:02X}");
+    }
+    let _ = write!(out, "{:02X}\r\n", !sum);
+}
+
+/// The highest address either format has to hold, which can be at most 32 bits
+fn highest_address(segments: &[LoadedSegment], entry: u64) -> Result<u32, ImageError> {
+    let highest = segments
+        .iter()
+        .map(|segment| segment.end() - 1)
+        .chain([entry])
+        .max()
+        .unwrap_or(0);
+    u32::try_from(highest).map_err(|_| ImageError::AddressOutOfRange(highest))
+}
+
+impl Elf {
+    /// The loadable segments as Intel HEX. Extended linear address records give the
+    /// upper 16 bits of each address, and the start record is the entry point: as
+    /// CS:IP if it's under 1 MiB, like objcopy, or as a linear address otherwise.
+    pub fn intel_hex(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<String, ImageError> {
+        let segments = self.loaded_segments(buffer, load_address)?;
+        let entry = self.header.entry;
+        highest_address(&segments, entry)?;
+
+        let mut out = String::new();
+        let mut upper_address = 0;
+        for segment in segments.iter() {
+            let mut address = segment.address;
+            let mut data = segment.data.as_slice();
+            while !data.is_empty() {
+                if address >> 16 != upper_address {
+                    upper_address = address >> 16;
+                    let upper = (upper_address as u16).to_be_bytes();
+                    write_intel_hex_record(&mut out, 0x04, 0, &upper);
+                }
+                // A record can't cross into the next 64 KiB, as its address is only 16 bits
+                let to_boundary = 0x10000 - (address & 0xffff) as usize;
+                let length = RECORD_DATA_LENGTH.min(to_boundary).min(data.len());
+                write_intel_hex_record(&mut out, 0x00, address as u16, &data[..length]);
+                address += length as u64;
+                data = &data[length..];
+            }
+        }
+
+        if entry != 0 {
+            match entry {
+                0..=0xfffff => {
+                    let segment = ((entry & 0xf0000) >> 4) as u16;
+                    let offset = entry as u16;
+                    let mut start = segment.to_be_bytes().to_vec();
+                    start.extend(offset.to_be_bytes());
+                    write_intel_hex_record(&mut out, 0x03, 0, &start);
+                }
+                _ => write_intel_hex_record(&mut out, 0x05, 0, &(entry as u32).to_be_bytes()),
+            }
+        }
+        write_intel_hex_record(&mut out, 0x01, 0, &[]);
+        Ok(out)
+    }
+
+    /// The loadable segments as Motorola S-records, after an S0 record holding `header`.
+    /// Every address is as wide as the highest one needs: S1 and S9 for 16 bits, S2 and
+    /// S8 for 24, S3 and S7 for 32. The last record gives the entry point.
+    pub fn srec(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+        header: &str,
+    ) -> Result<String, ImageError> {
+        let segments = self.loaded_segments(buffer, load_address)?;
+        let entry = self.header.entry;
+        let (data_type, end_type, address_length) = match highest_address(&segments, entry)? {
+            0..=0xffff => (1, 9, 2),
+            0x10000..=0xffffff => (2, 8, 3),
+            _ => (3, 7, 4),
+        };
+
+        let mut out = String::new();
+        // The count is a byte, which leaves room for 252 bytes of header
+        let header = &header.as_bytes()[..header.len().min(252)];
+        write_srec_record(&mut out, 0, 0, 2, header);
+        for segment in segments.iter() {
+            for (idx, data) in segment.data.chunks(RECORD_DATA_LENGTH).enumerate() {
+                let address = segment.address + (idx * RECORD_DATA_LENGTH) as u64;
+                write_srec_record(&mut out, data_type, address as u32, address_length, data);
+            }
+        }
+        write_srec_record(&mut out, end_type, entry as u32, address_length, &[]);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::{build_elf, Segment};
+    use crate::parse::parse_elf;
+
+    fn intel_hex(entry: u64, segments: &[Segment]) -> Result<String, ImageError> {
+        let buffer = build_elf(entry, segments);
+        let elf = parse_elf(&buffer).unwrap();
+        elf.intel_hex(&buffer, LoadAddress::Physical)
+    }
+
+    fn srec(entry: u64, segments: &[Segment]) -> String {
+        let buffer = build_elf(entry, segments);
+        let elf = parse_elf(&buffer).unwrap();
+        elf.srec(&buffer, LoadAddress::Physical, "hi").unwrap()
+    }
+
+    #[test]
+    fn record_checksums() {
+        let mut out = String::new();
+        write_intel_hex_record(&mut out, 0x00, 0x0030, &[0x02, 0x33, 0x7a]);
+        write_intel_hex_record(&mut out, 0x01, 0, &[]);
+        assert_eq!(out, ":0300300002337A1E\r\n:00000001FF\r\n");
+
+        let mut out = String::new();
+        let data = [0x0a, 0x0a, 0x0d, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        write_srec_record(&mut out, 1, 0x7af0, 2, &data);
+        assert_eq!(out, "S1137AF00A0A0D0000000000000000000000000061\r\n");
+    }
+
+    #[test]
+    fn intel_hex_splits_records_at_64_kib() {
+        let data: Vec<u8> = (0..16).collect();
+        let records = intel_hex(0, &[Segment::new(0xfff8, &data)]).unwrap();
+        assert_eq!(
+            records,
+            ":08FFF8000001020304050607E5\r\n\
+             :020000040001F9\r\n\
+             :0800000008090A0B0C0D0E0F9C\r\n\
+             :00000001FF\r\n"
+        );
+    }
+
+    #[test]
+    fn intel_hex_start_record() {
+        // Under 1 MiB it goes as CS:IP
+        let records = intel_hex(0x12345, &[Segment::new(0, &[])]).unwrap();
+        assert_eq!(records, ":040000031000234581\r\n:00000001FF\r\n");
+
+        let records = intel_hex(0x100000, &[Segment::new(0, &[])]).unwrap();
+        assert_eq!(records, ":0400000500100000E7\r\n:00000001FF\r\n");
+    }
+
+    #[test]
+    fn intel_hex_address_out_of_range() {
+        let result = intel_hex(0, &[Segment::new(0x1_0000_0000, &[1])]);
+        assert!(matches!(
+            result,
+            Err(ImageError::AddressOutOfRange(0x1_0000_0000))
+        ));
+
+        let result = intel_hex(0x1_0000_0000, &[Segment::new(0, &[1])]);
+        assert!(matches!(
+            result,
+            Err(ImageError::AddressOutOfRange(0x1_0000_0000))
+        ));
+    }
+
+    #[test]
+    fn srec_header() {
+        let records = srec(0x1000, &[Segment::new(0x1000, &[1, 2])]);
+        assert_eq!(records.lines().next(), Some("S0050000686929"));
+    }
+
+    #[test]
+    fn srec_address_width_fits_the_highest_address() {
+        let records = srec(0x1000, &[Segment::new(0x1000, &[1, 2])]);
+        assert_eq!(
+            records,
+            "S0050000686929\r\nS10510000102E7\r\nS9031000EC\r\n"
+        );
+
+        // The entry point counts too
+        let records = srec(0x10000, &[Segment::new(0x1000, &[1, 2])]);
+        assert_eq!(
+            records,
+            "S0050000686929\r\nS2060010000102E6\r\nS804010000FA\r\n"
+        );
+
+        let records = srec(0x1000000, &[Segment::new(0x1000000, &[1, 2])]);
+        assert_eq!(
+            records,
+            "S0050000686929\r\nS307010000000102F4\r\nS70501000000F9\r\n"
+        );
+    }
+}
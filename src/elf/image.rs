@@ -0,0 +1,331 @@
+//! Laying the PT_LOAD segments out as one raw image, the way `objcopy -O binary` does,
+//! for loaders that take bytes at an address rather than an ELF file.
+
+use std::cmp;
+use std::fmt::{Display, Formatter};
+
+use crate::elf::program_header::{HeaderType, ProgramHeader};
+use crate::elf::Elf;
+use crate::parse::{file_range, ParseError};
+
+/// Which of a segment's addresses to lay it out by
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum LoadAddress {
+    /// `p_paddr`, where the segment is stored, e.g. in ROM. What objcopy uses.
+    #[default]
+    Physical,
+    /// `p_vaddr`, where the segment runs
+    Virtual,
+}
+
+impl LoadAddress {
+    pub fn of(&self, ph: &ProgramHeader) -> u64 {
+        match self {
+            LoadAddress::Physical => ph.physical_address,
+            LoadAddress::Virtual => ph.virtual_address,
+        }
+    }
+}
+
+/// The file bytes of one PT_LOAD segment, and where they go. The zero-filled part
+/// past `size_in_file` isn't included, as it's not part of the image.
+pub struct LoadedSegment {
+    pub index: usize, // Into the program header table
+    pub address: u64,
+    pub data: Vec<u8>,
+}
+
+impl LoadedSegment {
+    /// One past the last address the segment's bytes go to
+    pub fn end(&self) -> u64 {
+        self.address + self.data.len() as u64
+    }
+}
+
+/// Largest image `flat_binary` lays out by default. Segments far apart, like a
+/// p_paddr of 0 next to one at 0x80000000, would otherwise fill gigabytes with padding.
+pub const MAX_FLAT_BINARY_SIZE: u64 = 1 << 30;
+
+/// How to lay out a flat binary
+#[derive(Debug)]
+pub struct FlatBinaryOptions {
+    pub load_address: LoadAddress,
+    /// Written between segments, and as padding
+    pub gap_fill: u8,
+    /// Address of the image's first byte, instead of the lowest segment's. Anything
+    /// below it is left out.
+    pub start_address: Option<u64>,
+    /// Pad the image out to at least this many bytes
+    pub pad_to: Option<u64>,
+    /// Images larger than this many bytes are an error rather than laid out
+    pub max_size: u64,
+}
+
+impl Default for FlatBinaryOptions {
+    fn default() -> Self {
+        FlatBinaryOptions {
+            load_address: LoadAddress::default(),
+            gap_fill: 0,
+            start_address: None,
+            pad_to: None,
+            max_size: MAX_FLAT_BINARY_SIZE,
+        }
+    }
+}
+
+/// Why the segments couldn't be laid out
+#[derive(Debug)]
+pub enum ImageError {
+    /// Reading a segment's bytes out of the file failed
+    ParseError(ParseError),
+    /// Two segments want the same addresses, given as program header indexes
+    OverlappingSegments((usize, usize)),
+    /// The image spans more bytes than the size limit, or than fit in memory
+    TooLarge(u64),
+    /// The address is too large for the output format
+    AddressOutOfRange(u64),
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            ImageError::ParseError(ref err) => Some(err),
+            ImageError::OverlappingSegments(_) => None,
+            ImageError::TooLarge(_) => None,
+            ImageError::AddressOutOfRange(_) => None,
+        }
+    }
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ImageError::ParseError(ref err) => err.fmt(f),
+            ImageError::OverlappingSegments((first, second)) => {
+                write!(f, "Segments {first:02} and {second:02} overlap")
+            }
+            ImageError::TooLarge(length) => {
+                write!(f, "Image of {length:#x} bytes is too large")
+            }
+            ImageError::AddressOutOfRange(address) => {
+                write!(f, "Address {address:#x} doesn't fit in the output format")
+            }
+        }
+    }
+}
+
+impl From<ParseError> for ImageError {
+    fn from(err: ParseError) -> Self {
+        ImageError::ParseError(err)
+    }
+}
+
+impl Elf {
+    /// The PT_LOAD segments with bytes in the file, in address order. Segments that
+    /// overlap at `load_address` are an error, since the image can't hold both.
+    pub fn loaded_segments(
+        &self,
+        buffer: &[u8],
+        load_address: LoadAddress,
+    ) -> Result<Vec<LoadedSegment>, ImageError> {
+        let mut segments = Vec::new();
+        for (index, ph) in self.program_header_table.iter().enumerate() {
+            if ph.header_type != HeaderType::Load || ph.size_in_file == 0 {
+                continue;
+            }
+            let address = load_address.of(ph);
+            // So `LoadedSegment::end` can't overflow
+            address
+                .checked_add(ph.size_in_file)
+                .ok_or(ParseError::IntegerOverflow)?;
+            let data = file_range(buffer, ph.offset, ph.size_in_file)?;
+            segments.push(LoadedSegment {
+                index,
+                address,
+                data: data.to_vec(),
+            });
+        }
+
+        segments.sort_by_key(|segment| segment.address);
+        for pair in segments.windows(2) {
+            if pair[1].address < pair[0].end() {
+                let (first, second) = (pair[0].index, pair[1].index);
+                return Err(ImageError::OverlappingSegments((
+                    first.min(second),
+                    first.max(second),
+                )));
+            }
+        }
+        Ok(segments)
+    }
+
+    /// The loadable segments as one raw image, gaps filled in, the way
+    /// `objcopy -O binary` writes it. `ImageError::TooLarge` if it would be over
+    /// `options.max_size` bytes.
+    pub fn flat_binary(
+        &self,
+        buffer: &[u8],
+        options: &FlatBinaryOptions,
+    ) -> Result<Vec<u8>, ImageError> {
+        let segments = self.loaded_segments(buffer, options.load_address)?;
+        let lowest = segments.first().map_or(0, |segment| segment.address);
+        let start = options.start_address.unwrap_or(lowest);
+        let end = segments
+            .iter()
+            .map(LoadedSegment::end)
+            .max()
+            .unwrap_or(start);
+
+        let length = end.saturating_sub(start);
+        let length = cmp::max(length, options.pad_to.unwrap_or(0));
+        if length > options.max_size {
+            return Err(ImageError::TooLarge(length));
+        }
+        let length = usize::try_from(length).map_err(|_| ImageError::TooLarge(length))?;
+        let mut image = Vec::new();
+        image
+            .try_reserve_exact(length)
+            .map_err(|_| ImageError::TooLarge(length as u64))?;
+        image.resize(length, options.gap_fill);
+
+        for segment in segments.iter().filter(|segment| segment.end() > start) {
+            // Only the part from `start` on, if the segment begins below it
+            let skipped = start.saturating_sub(segment.address) as usize;
+            let image_offset = segment.address.saturating_sub(start) as usize;
+            let data = &segment.data[skipped..];
+            image[image_offset..image_offset + data.len()].copy_from_slice(data);
+        }
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::{build_elf, Segment};
+    use crate::parse::parse_elf;
+
+    fn flat_binary(
+        segments: &[Segment],
+        options: &FlatBinaryOptions,
+    ) -> Result<Vec<u8>, ImageError> {
+        let buffer = build_elf(0, segments);
+        let elf = parse_elf(&buffer).unwrap();
+        elf.flat_binary(&buffer, options)
+    }
+
+    #[test]
+    fn gap_between_segments_is_filled() {
+        let segments = [Segment::new(0x1000, &[1, 2]), Segment::new(0x1004, &[3])];
+        let options = FlatBinaryOptions {
+            gap_fill: 0xff,
+            ..Default::default()
+        };
+        let image = flat_binary(&segments, &options).unwrap();
+        assert_eq!(image, [1, 2, 0xff, 0xff, 3]);
+    }
+
+    #[test]
+    fn start_address_above_lowest_segment_drops_leading_bytes() {
+        let segments = [Segment::new(0x1000, &[1, 2, 3]), Segment::new(0x1004, &[4])];
+        let options = FlatBinaryOptions {
+            start_address: Some(0x1002),
+            ..Default::default()
+        };
+        let image = flat_binary(&segments, &options).unwrap();
+        assert_eq!(image, [3, 0, 4]);
+    }
+
+    #[test]
+    fn start_address_below_lowest_segment_pads_the_front() {
+        let segments = [Segment::new(0x1000, &[1])];
+        let options = FlatBinaryOptions {
+            start_address: Some(0xffe),
+            gap_fill: 0xee,
+            ..Default::default()
+        };
+        let image = flat_binary(&segments, &options).unwrap();
+        assert_eq!(image, [0xee, 0xee, 1]);
+    }
+
+    #[test]
+    fn pad_to_only_ever_grows_the_image() {
+        let segments = [Segment::new(0x1000, &[1, 2, 3])];
+        let smaller = FlatBinaryOptions {
+            pad_to: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(flat_binary(&segments, &smaller).unwrap(), [1, 2, 3]);
+
+        let larger = FlatBinaryOptions {
+            pad_to: Some(5),
+            gap_fill: 0xff,
+            ..Default::default()
+        };
+        assert_eq!(
+            flat_binary(&segments, &larger).unwrap(),
+            [1, 2, 3, 0xff, 0xff]
+        );
+    }
+
+    #[test]
+    fn overlapping_segments_are_an_error() {
+        let segments = [
+            Segment::new(0x1002, &[3, 4]),
+            Segment::new(0x1000, &[1, 2, 3]),
+        ];
+        let result = flat_binary(&segments, &FlatBinaryOptions::default());
+        assert!(matches!(
+            result,
+            Err(ImageError::OverlappingSegments((0, 1)))
+        ));
+    }
+
+    #[test]
+    fn bss_is_left_out_of_the_image() {
+        let mut with_bss = Segment::new(0x1000, &[1, 2]);
+        with_bss.size_in_memory = 0x100;
+        let segments = [with_bss, Segment::new(0x1004, &[3])];
+        let options = FlatBinaryOptions {
+            gap_fill: 0xff,
+            ..Default::default()
+        };
+        // Overlapping in memory is fine, as only the file bytes are laid out
+        let image = flat_binary(&segments, &options).unwrap();
+        assert_eq!(image, [1, 2, 0xff, 0xff, 3]);
+    }
+
+    #[test]
+    fn segments_are_laid_out_by_the_chosen_address() {
+        let mut first = Segment::new(0x1000, &[1]);
+        first.virtual_address = 0x8000;
+        let mut second = Segment::new(0x1002, &[2]);
+        second.virtual_address = 0x8004;
+        let segments = [first, second];
+
+        let physical = flat_binary(&segments, &FlatBinaryOptions::default()).unwrap();
+        assert_eq!(physical, [1, 0, 2]);
+
+        let options = FlatBinaryOptions {
+            load_address: LoadAddress::Virtual,
+            ..Default::default()
+        };
+        let virtual_image = flat_binary(&segments, &options).unwrap();
+        assert_eq!(virtual_image, [1, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn images_over_the_size_limit_are_an_error() {
+        let segments = [Segment::new(0, &[1]), Segment::new(0x8000_0000, &[2])];
+        let result = flat_binary(&segments, &FlatBinaryOptions::default());
+        assert!(matches!(result, Err(ImageError::TooLarge(0x8000_0001))));
+
+        let options = FlatBinaryOptions {
+            pad_to: Some(0x11),
+            max_size: 0x10,
+            ..Default::default()
+        };
+        let result = flat_binary(&[Segment::new(0, &[1])], &options);
+        assert!(matches!(result, Err(ImageError::TooLarge(0x11))));
+    }
+}
@@ -0,0 +1,749 @@
+//! The `--json` output. Each file becomes one JSON object on a line of its own, holding
+//! only the keys for the dumps that were asked for. Fields are named after the struct
+//! fields they come from, and a decoded field sits next to its raw value with a `_name`
+//! suffix, e.g. `"type": 3, "type_name": "DYN"`. Decoded names are spelled the way the
+//! text output spells them. Numbers are always the raw integers, never hex strings.
+//!
+//! The schema only grows: keys may be added, but existing ones keep their name, type and
+//! meaning for as long as `schema_version` stays the same. Missing data is `null`, never
+//! an absent key.
+//!
+//! ```text
+//! {
+//!   "schema_version": 1,
+//!   "file": string,
+//!   "warnings": [string],                                     --lenient, unreadable names
+//!   "file_header": {                                          -h
+//!     "ident": [16 × number],
+//!     "class", "data", "ident_version", "os_abi", "abi_version": number,
+//!     "class_name", "data_name": string, "os_abi_name": string | null,
+//!     "type": number, "type_name": string,
+//!     "machine": number, "machine_name": string | null,
+//!     "version", "entry", "program_header_offset", "section_header_offset": number,
+//!     "flags": number, "flags_name": string,
+//!     "elf_header_size", "program_header_entry_size", "program_header_entries",
+//!     "section_header_entry_size", "section_header_entries", "string_table_index": number,
+//!     "stored_program_header_entries", "stored_section_header_entries",
+//!     "stored_string_table_index": number     e_phnum, e_shnum, e_shstrndx before
+//!                                             resolving PN_XNUM/SHN_XINDEX escapes
+//!   },
+//!   "program_headers": [{                                     -l
+//!     "type": number, "type_name": string,
+//!     "offset", "virtual_address", "physical_address", "size_in_file",
+//!     "size_in_memory", "alignment": number,
+//!     "flags": number, "flags_name": string,
+//!     "sections": [string]
+//!   }],
+//!   "section_headers": [{                                     -S
+//!     "index": number, "name": string,
+//!     "type": number, "type_name": string,
+//!     "flags": number, "flags_name": string,
+//!     "address", "offset", "size", "link", "info", "alignment", "entry_size": number
+//!   }],
+//!   "symbol_tables": [{                                       -s
+//!     "section_index": number, "section": string,
+//!     "symbols": [{
+//!       "index": number, "name": string,
+//!       "value", "size": number,
+//!       "type": number, "type_name": string,
+//!       "binding": number, "binding_name": string,
+//!       "visibility": number, "visibility_name": string,
+//!       "shndx": number, "section_index": number, "section_index_name": string,
+//!       "version": number | null, "version_name": string | null
+//!     }]
+//!   }],
+//!   "relocation_tables": [{                                   -r
+//!     "section_index": number, "section": string, "offset": number,
+//!     "relocations": [{
+//!       "offset", "info": number,
+//!       "type": number, "type_name": string,
+//!       "symbol_index": number, "symbol_name": string | null,
+//!       "symbol_value": number | null, "addend": number | null
+//!     }]
+//!   }],
+//!   "dynamic": null | {                                       -d
+//!     "offset": number,
+//!     "entries": [{
+//!       "tag": number, "tag_name": string,
+//!       "value": number, "value_name": string, "string": string | null
+//!     }]
+//!   },
+//!   "version_info": {                                         -V
+//!     "definitions": null | {
+//!       "section_index": number, "section": string,
+//!       "entries": [{
+//!         "offset", "version", "index": number,
+//!         "flags": number, "flags_name": string,
+//!         "name": string, "parents": [string]
+//!       }]
+//!     },
+//!     "needs": null | {
+//!       "section_index": number, "section": string,
+//!       "entries": [{
+//!         "offset", "version": number, "file": string,
+//!         "requirements": [{
+//!           "offset", "index": number, "name": string,
+//!           "flags": number, "flags_name": string
+//!         }]
+//!       }]
+//!     },
+//!     "symbols": null | {
+//!       "section_index": number, "section": string,
+//!       "versions": [{ "value": number, "name": string | null }]
+//!     }
+//!   },
+//!   "histograms": [{                                          -I
+//!     "section_index": number, "section": string,
+//!     "section_type": number, "section_type_name": string,
+//!     "buckets": number, "symbols": number,
+//!     "bucket_length_counts": [number]   // Index is the length, value the bucket count
+//!   }],
+//!   "notes": [{                                               -n
+//!     "section_index": number | null, "section": string | null,
+//!     "offset", "size": number,
+//!     "notes": [{
+//!       "owner": string, "type": number, "type_name": string,
+//!       "descriptor": string,            // Hex bytes
+//!       "description": [string]          // Decoded lines, as the text output shows them
+//!     }]
+//!   }],
+//!   "lookups": [{                                             --lookup
+//!     "name": string, "version": string | null,
+//!     "hash_section_index": number | null, "hash_section": string | null,
+//!     "hash": number | null, "bloom_filter_matches": bool | null,
+//!     "symbol": null | {
+//!       "index": number, "name": string, "version_name": string | null,
+//!       "value", "size": number, "type_name", "binding_name": string
+//!     }
+//!   }],
+//!   "required_versions": [{                                   --required-versions
+//!     "library": string, "version": string
+//!   }]
+//! }
+//! ```
+
+use crate::elf::hash;
+use crate::elf::program_header::ProgramHeader;
+use crate::elf::symbol::{Symbol, SymbolTable};
+use crate::elf::{Elf, Selection, SymbolVersion};
+use crate::json::Json;
+use crate::{abi, to_str};
+
+/// Bump when a key changes name, type or meaning. Adding keys doesn't need it.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// The whole object for one file: the dumps in `selection`, and the warnings parsing it
+/// gave. `file_path` is only used to name it.
+pub fn document(elf: &Elf, file_path: &str, selection: &Selection) -> Json {
+    let mut fields = vec![
+        ("schema_version", SCHEMA_VERSION.into()),
+        ("file", file_path.into()),
+        (
+            "warnings",
+            Json::array(elf.warnings.iter().map(|warning| warning.to_string())),
+        ),
+    ];
+    let dumps = [
+        (
+            selection.file_header,
+            "file_header",
+            Elf::file_header_json as fn(&Elf) -> Json,
+        ),
+        (
+            selection.program_headers,
+            "program_headers",
+            Elf::program_headers_json,
+        ),
+        (
+            selection.section_headers,
+            "section_headers",
+            Elf::section_headers_json,
+        ),
+        (selection.symbols, "symbol_tables", Elf::symbol_tables_json),
+        (
+            selection.relocations,
+            "relocation_tables",
+            Elf::relocation_tables_json,
+        ),
+        (selection.dynamic, "dynamic", Elf::dynamic_json),
+        (
+            selection.version_info,
+            "version_info",
+            Elf::version_info_json,
+        ),
+        (selection.histogram, "histograms", Elf::histograms_json),
+        (selection.notes, "notes", Elf::notes_json),
+    ];
+    for (selected, key, dump) in dumps {
+        if selected {
+            fields.push((key, dump(elf)));
+        }
+    }
+    if !selection.lookups.is_empty() {
+        let lookups = selection
+            .lookups
+            .iter()
+            .map(|lookup| elf.symbol_lookup_json(&lookup.name, lookup.version.as_deref()));
+        fields.push(("lookups", Json::array(lookups)));
+    }
+    if selection.required_versions {
+        fields.push(("required_versions", elf.minimum_library_versions_json()));
+    }
+    Json::Object(fields)
+}
+
+impl Elf {
+    fn section_name_json(&self, section_index: usize) -> Json {
+        let name = self
+            .section_header_table
+            .get(section_index)
+            .map(|section| section.name.as_str());
+        name.into()
+    }
+
+    pub fn file_header_json(&self) -> Json {
+        let header = &self.header;
+        let flags_name = to_str::e_flags_to_string(header.machine.0, header.flags);
+        Json::object([
+            ("ident", Json::array(header.ident)),
+            ("class", header.ident[abi::EI_CLASS].into()),
+            ("class_name", header.class.to_string().into()),
+            ("data", header.ident[abi::EI_DATA].into()),
+            ("data_name", header.endianness.to_string().into()),
+            ("ident_version", header.ident[abi::EI_VERSION].into()),
+            ("os_abi", header.os_abi.0.into()),
+            (
+                "os_abi_name",
+                to_str::e_osabi_to_human_string(header.os_abi.0).into(),
+            ),
+            ("abi_version", header.abi_version.into()),
+            ("type", header.file_type.value().into()),
+            ("type_name", header.file_type.name().into()),
+            ("machine", header.machine.0.into()),
+            (
+                "machine_name",
+                to_str::e_machine_to_human_str(header.machine.0).into(),
+            ),
+            ("version", header.version.into()),
+            ("entry", header.entry.into()),
+            ("program_header_offset", header.program_header_offset.into()),
+            ("section_header_offset", header.section_header_offset.into()),
+            ("flags", header.flags.into()),
+            ("flags_name", flags_name.trim_start_matches(", ").into()),
+            ("elf_header_size", header.elf_header_size.into()),
+            (
+                "program_header_entry_size",
+                header.program_header_entry_size.into(),
+            ),
+            (
+                "program_header_entries",
+                header.program_header_entries.into(),
+            ),
+            (
+                "section_header_entry_size",
+                header.section_header_entry_size.into(),
+            ),
+            (
+                "section_header_entries",
+                header.section_header_entries.into(),
+            ),
+            ("string_table_index", header.string_table_index.into()),
+            (
+                "stored_program_header_entries",
+                header.stored_program_header_entries.into(),
+            ),
+            (
+                "stored_section_header_entries",
+                header.stored_section_header_entries.into(),
+            ),
+            (
+                "stored_string_table_index",
+                header.stored_string_table_index.into(),
+            ),
+        ])
+    }
+
+    fn program_header_json(&self, ph: &ProgramHeader) -> Json {
+        let sections: Vec<&str> = self
+            .sections_in_segment(ph)
+            .map(|section| section.name.as_str())
+            .collect();
+        Json::object([
+            ("type", ph.header_type.value().into()),
+            (
+                "type_name",
+                ph.header_type.name(self.header.machine.0).into(),
+            ),
+            ("offset", ph.offset.into()),
+            ("virtual_address", ph.virtual_address.into()),
+            ("physical_address", ph.physical_address.into()),
+            ("size_in_file", ph.size_in_file.into()),
+            ("size_in_memory", ph.size_in_memory.into()),
+            ("alignment", ph.alignment.into()),
+            ("flags", ph.flags.0.into()),
+            ("flags_name", ph.flags.to_string().into()),
+            ("sections", sections.into()),
+        ])
+    }
+
+    pub fn program_headers_json(&self) -> Json {
+        Json::array(
+            self.program_header_table
+                .iter()
+                .map(|ph| self.program_header_json(ph)),
+        )
+    }
+
+    pub fn section_headers_json(&self) -> Json {
+        Json::array(
+            self.section_header_table
+                .iter()
+                .enumerate()
+                .map(|(idx, sh)| {
+                    Json::object([
+                        ("index", idx.into()),
+                        ("name", sh.name.as_str().into()),
+                        ("type", sh.section_type.into()),
+                        ("type_name", sh.type_name().into()),
+                        ("flags", sh.flags.into()),
+                        ("flags_name", to_str::sh_flags_to_string(sh.flags).into()),
+                        ("address", sh.address.into()),
+                        ("offset", sh.offset.into()),
+                        ("size", sh.size.into()),
+                        ("link", sh.link.into()),
+                        ("info", sh.info.into()),
+                        ("alignment", sh.alignment.into()),
+                        ("entry_size", sh.entry_size.into()),
+                    ])
+                }),
+        )
+    }
+
+    /// The .gnu.version entry of a dynamic symbol, and the version the text output shows
+    /// after its name
+    fn symbol_version_json(&self, table: &SymbolTable, symbol_idx: usize) -> (Json, Json) {
+        let section = &self.section_header_table[table.section_index];
+        let version = self
+            .version_symbols
+            .as_ref()
+            .filter(|_| section.section_type == abi::SHT_DYNSYM)
+            .and_then(|versions| versions.versions.get(symbol_idx).copied());
+        let Some(version) = version else {
+            return (Json::Null, Json::Null);
+        };
+        let name = match self.symbol_version(table, symbol_idx) {
+            Some(
+                SymbolVersion::Default(name)
+                | SymbolVersion::Hidden(name)
+                | SymbolVersion::Needed(name, _),
+            ) => Some(name),
+            None => None,
+        };
+        (version.into(), name.into())
+    }
+
+    fn symbol_json(&self, table: &SymbolTable, idx: usize, symbol: &Symbol) -> Json {
+        let (version, version_name) = self.symbol_version_json(table, idx);
+        Json::object([
+            ("index", idx.into()),
+            ("name", self.symbol_display_name(symbol).into()),
+            ("value", symbol.value.into()),
+            ("size", symbol.size.into()),
+            ("type", symbol.symbol_type().into()),
+            ("type_name", symbol.type_name().into()),
+            ("binding", symbol.binding().into()),
+            ("binding_name", symbol.binding_name().into()),
+            ("visibility", symbol.visibility().into()),
+            ("visibility_name", symbol.visibility_name().into()),
+            ("shndx", symbol.shndx.into()),
+            ("section_index", symbol.section_index.into()),
+            ("section_index_name", symbol.section_index_name().into()),
+            ("version", version),
+            ("version_name", version_name),
+        ])
+    }
+
+    pub fn symbol_tables_json(&self) -> Json {
+        Json::array(self.symbol_tables.iter().map(|table| {
+            let symbols = table
+                .symbols
+                .iter()
+                .enumerate()
+                .map(|(idx, symbol)| self.symbol_json(table, idx, symbol));
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", self.section_name_json(table.section_index)),
+                ("symbols", Json::array(symbols)),
+            ])
+        }))
+    }
+
+    pub fn relocation_tables_json(&self) -> Json {
+        Json::array(self.relocation_tables.iter().map(|table| {
+            let section = &self.section_header_table[table.section_index];
+            let symbol_table = self
+                .symbol_tables
+                .iter()
+                .find(|symbol_table| symbol_table.section_index == section.link as usize);
+            let relocations = table.relocations.iter().map(|relocation| {
+                let symbol = match relocation.symbol_index {
+                    0 => None,
+                    idx => symbol_table.and_then(|table| table.symbols.get(idx as usize)),
+                };
+                Json::object([
+                    ("offset", relocation.offset.into()),
+                    ("info", relocation.info.into()),
+                    ("type", relocation.relocation_type.into()),
+                    (
+                        "type_name",
+                        to_str::r_type_to_string(self.header.machine.0, relocation.relocation_type)
+                            .into(),
+                    ),
+                    ("symbol_index", relocation.symbol_index.into()),
+                    (
+                        "symbol_name",
+                        symbol.map(|symbol| self.symbol_display_name(symbol)).into(),
+                    ),
+                    ("symbol_value", symbol.map(|symbol| symbol.value).into()),
+                    ("addend", relocation.addend.into()),
+                ])
+            });
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", section.name.as_str().into()),
+                ("offset", section.offset.into()),
+                ("relocations", Json::array(relocations)),
+            ])
+        }))
+    }
+
+    pub fn dynamic_json(&self) -> Json {
+        let Some(dynamic) = &self.dynamic else {
+            return Json::Null;
+        };
+        let entries = dynamic.entries.iter().map(|entry| {
+            Json::object([
+                ("tag", entry.tag.into()),
+                ("tag_name", entry.tag_name().into()),
+                ("value", entry.value.into()),
+                ("value_name", self.dynamic_entry_value(entry).into()),
+                ("string", entry.string.as_deref().into()),
+            ])
+        });
+        Json::object([
+            ("offset", dynamic.offset.into()),
+            ("entries", Json::array(entries)),
+        ])
+    }
+
+    pub fn version_info_json(&self) -> Json {
+        let definitions = self.version_definitions.as_ref().map(|table| {
+            let entries = table.definitions.iter().map(|definition| {
+                let mut names = definition.names.iter().map(|name| name.name.as_str());
+                Json::object([
+                    ("offset", definition.offset.into()),
+                    ("version", definition.version.into()),
+                    ("index", definition.index.into()),
+                    ("flags", definition.flags.into()),
+                    (
+                        "flags_name",
+                        to_str::ver_flags_to_string(definition.flags).into(),
+                    ),
+                    ("name", names.next().unwrap_or_default().into()),
+                    ("parents", Json::array(names)),
+                ])
+            });
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", self.section_name_json(table.section_index)),
+                ("entries", Json::array(entries)),
+            ])
+        });
+        let needs = self.version_needs.as_ref().map(|table| {
+            let entries = table.needs.iter().map(|need| {
+                let requirements = need.requirements.iter().map(|requirement| {
+                    Json::object([
+                        ("offset", requirement.offset.into()),
+                        ("index", requirement.index.into()),
+                        ("name", requirement.name.as_str().into()),
+                        ("flags", requirement.flags.into()),
+                        (
+                            "flags_name",
+                            to_str::ver_flags_to_string(requirement.flags).into(),
+                        ),
+                    ])
+                });
+                Json::object([
+                    ("offset", need.offset.into()),
+                    ("version", need.version.into()),
+                    ("file", need.file.as_str().into()),
+                    ("requirements", Json::array(requirements)),
+                ])
+            });
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", self.section_name_json(table.section_index)),
+                ("entries", Json::array(entries)),
+            ])
+        });
+        let symbols = self.version_symbols.as_ref().map(|table| {
+            let link = self.section_header_table[table.section_index].link as usize;
+            let symbol_table = self
+                .symbol_tables
+                .iter()
+                .find(|symbol_table| symbol_table.section_index == link);
+            let versions = table.versions.iter().enumerate().map(|(idx, &version)| {
+                let name = self.version_symbol_name(symbol_table, idx, version);
+                Json::object([("value", version.into()), ("name", name.into())])
+            });
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", self.section_name_json(table.section_index)),
+                ("versions", Json::array(versions)),
+            ])
+        });
+        Json::object([
+            ("definitions", definitions.into()),
+            ("needs", needs.into()),
+            ("symbols", symbols.into()),
+        ])
+    }
+
+    fn histogram_json(&self, section_index: usize, lengths: &[usize]) -> Json {
+        let section_type = self.section_header_table[section_index].section_type;
+        Json::object([
+            ("section_index", section_index.into()),
+            ("section", self.section_name_json(section_index)),
+            ("section_type", section_type.into()),
+            (
+                "section_type_name",
+                self.section_header_table[section_index].type_name().into(),
+            ),
+            ("buckets", lengths.len().into()),
+            ("symbols", lengths.iter().sum::<usize>().into()),
+            (
+                "bucket_length_counts",
+                Json::array(Self::bucket_length_counts(lengths)),
+            ),
+        ])
+    }
+
+    pub fn histograms_json(&self) -> Json {
+        let mut histograms = Vec::new();
+        if let Some(table) = &self.hash_table {
+            histograms.push(self.histogram_json(table.section_index, &table.chain_lengths()));
+        }
+        if let Some(table) = &self.gnu_hash_table {
+            histograms.push(self.histogram_json(table.section_index, &table.chain_lengths()));
+        }
+        Json::Array(histograms)
+    }
+
+    pub fn notes_json(&self) -> Json {
+        Json::array(self.note_tables.iter().map(|table| {
+            let notes = table.notes.iter().map(|note| {
+                let descriptor: String = note
+                    .descriptor
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                let description = self
+                    .note_description(note)
+                    .into_iter()
+                    .map(|line| line.trim().to_string());
+                Json::object([
+                    ("owner", note.name.as_str().into()),
+                    ("type", note.note_type.into()),
+                    ("type_name", self.note_type_name(note).into()),
+                    ("descriptor", descriptor.into()),
+                    ("description", Json::array(description)),
+                ])
+            });
+            let section = match table.section_index {
+                Some(section_index) => self.section_name_json(section_index),
+                None => Json::Null,
+            };
+            Json::object([
+                ("section_index", table.section_index.into()),
+                ("section", section),
+                ("offset", table.offset.into()),
+                ("size", table.size.into()),
+                ("notes", Json::array(notes)),
+            ])
+        }))
+    }
+
+    pub fn symbol_lookup_json(&self, name: &str, version: Option<&str>) -> Json {
+        let (section_index, hash, bloom) = match (&self.gnu_hash_table, &self.hash_table) {
+            (Some(table), _) => {
+                let hash = hash::gnu_hash(name.as_bytes());
+                let bloom = table.bloom_filter_matches(hash);
+                (Some(table.section_index), Some(hash), Some(bloom))
+            }
+            (None, Some(table)) => (
+                Some(table.section_index),
+                Some(hash::elf_hash(name.as_bytes())),
+                None,
+            ),
+            (None, None) => (None, None, None),
+        };
+        let symbol = self
+            .lookup_dynamic_symbol(name, version)
+            .map(|(idx, symbol)| {
+                let version_name = section_index
+                    .and_then(|section_index| self.hashed_symbol_table(section_index))
+                    .map(|table| self.symbol_version_json(table, idx).1)
+                    .unwrap_or(Json::Null);
+                Json::object([
+                    ("index", idx.into()),
+                    ("name", symbol.name.as_str().into()),
+                    ("version_name", version_name),
+                    ("value", symbol.value.into()),
+                    ("size", symbol.size.into()),
+                    ("type_name", symbol.type_name().into()),
+                    ("binding_name", symbol.binding_name().into()),
+                ])
+            });
+        let hash_section = match section_index {
+            Some(section_index) => self.section_name_json(section_index),
+            None => Json::Null,
+        };
+        Json::object([
+            ("name", name.into()),
+            ("version", version.into()),
+            ("hash_section_index", section_index.into()),
+            ("hash_section", hash_section),
+            ("hash", hash.into()),
+            ("bloom_filter_matches", bloom.into()),
+            ("symbol", symbol.into()),
+        ])
+    }
+
+    pub fn minimum_library_versions_json(&self) -> Json {
+        Json::array(
+            self.minimum_library_versions()
+                .into_iter()
+                .map(|(file, version)| {
+                    Json::object([
+                        ("library", file.into()),
+                        ("version", version.to_string().into()),
+                    ])
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::header::{Class, Endianness};
+    use crate::elf::testing::{build_elf_with, build_versioned_elf, Section, Segment};
+    use crate::parse::{parse_elf, section_data};
+
+    /// The value of `key` in an object
+    fn field<'a>(json: &'a Json, key: &str) -> &'a Json {
+        match json {
+            Json::Object(fields) => &fields.iter().find(|(name, _)| *name == key).unwrap().1,
+            _ => panic!("not an object: {json}"),
+        }
+    }
+
+    fn elements(json: &Json) -> &[Json] {
+        match json {
+            Json::Array(values) => values,
+            _ => panic!("not an array: {json}"),
+        }
+    }
+
+    #[test]
+    fn symbol_versions_match_the_text() {
+        let elf = parse_elf(&build_versioned_elf()).unwrap();
+        let tables = elf.symbol_tables_json();
+        let symbols = field(&elements(&tables)[0], "symbols");
+        let versions: Vec<String> = elements(symbols)
+            .iter()
+            .map(|symbol| field(symbol, "version_name").to_string())
+            .collect();
+        // Named where the text output gives a suffix, and null where it gives none
+        let expected = [
+            "null",
+            "\"LIBX_2.0\"",
+            "\"LIBX_2.0\"",
+            "\"GLIBC_2.34\"",
+            "null",
+            "null",
+            "null",
+        ];
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn segment_flags_match_the_text() {
+        let mut elf = parse_elf(&fixture()).unwrap();
+        // A PF_MASKOS bit, which the text output leaves out of the Flg column
+        elf.program_header_table[0].flags.0 |= 0x00100000;
+        let headers = elf.program_headers_json();
+        let header = &elements(&headers)[0];
+        assert_eq!(field(header, "flags").to_string(), "1048581");
+        assert_eq!(field(header, "flags_name").to_string(), "\"R E\"");
+    }
+
+    /// An ELF32 executable with one PT_LOAD, `.text`, a section named with quotes and a
+    /// tab, and one whose name isn't UTF-8
+    fn fixture() -> Vec<u8> {
+        let mut text = Section::new(".text", abi::SHT_PROGBITS, vec![0xc3]);
+        text.flags = (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64;
+        text.address = 0x401000;
+        let mut buffer = build_elf_with(
+            Class::Elf32,
+            Endianness::Little,
+            0x401000,
+            &[Segment::new(0x401000, &[0xc3])],
+            &[
+                text,
+                Section::new(".\"quoted\"\t", abi::SHT_PROGBITS, vec![]),
+                Section::new(".bad", abi::SHT_PROGBITS, vec![]),
+            ],
+        );
+        let elf = parse_elf(&buffer).unwrap();
+        let string_table = &elf.section_header_table[elf.header.string_table_index];
+        let names = section_data(&buffer, string_table).unwrap();
+        let bad = names.windows(4).position(|name| name == b".bad").unwrap();
+        buffer[string_table.offset as usize + bad + 1] = 0xff;
+        buffer
+    }
+
+    #[test]
+    fn document_shape() {
+        let elf = parse_elf(&fixture()).unwrap();
+        let selection = Selection {
+            program_headers: true,
+            section_headers: true,
+            ..Selection::default()
+        };
+        let document = document(&elf, "a \"b\".out", &selection).to_string();
+        let expected = concat!(
+            r#"{"schema_version":1,"file":"a \"b\".out","#,
+            r#""warnings":["invalid utf-8 sequence of 1 bytes from index 1"],"#,
+            r#""program_headers":[{"type":1,"type_name":"LOAD","offset":84,"#,
+            r#""virtual_address":4198400,"physical_address":4198400,"size_in_file":1,"#,
+            r#""size_in_memory":1,"alignment":1,"flags":5,"flags_name":"R E","sections":[]}],"#,
+            r#""section_headers":["#,
+            r#"{"index":0,"name":"","type":0,"type_name":"NULL","flags":0,"flags_name":"","#,
+            r#""address":0,"offset":0,"size":0,"link":0,"info":0,"alignment":0,"entry_size":0},"#,
+            r#"{"index":1,"name":".text","type":1,"type_name":"PROGBITS","flags":6,"#,
+            r#""flags_name":"AX","address":4198400,"offset":85,"size":1,"link":0,"info":0,"#,
+            r#""alignment":1,"entry_size":0},"#,
+            r#"{"index":2,"name":".\"quoted\"\t","type":1,"type_name":"PROGBITS","flags":0,"#,
+            r#""flags_name":"","address":0,"offset":86,"size":0,"link":0,"info":0,"#,
+            r#""alignment":1,"entry_size":0},"#,
+            r#"{"index":3,"name":"<corrupt>","type":1,"type_name":"PROGBITS","flags":0,"#,
+            r#""flags_name":"","address":0,"offset":86,"size":0,"link":0,"info":0,"#,
+            r#""alignment":1,"entry_size":0},"#,
+            r#"{"index":4,"name":".shstrtab","type":3,"type_name":"STRTAB","flags":0,"#,
+            r#""flags_name":"","address":0,"offset":86,"size":33,"link":0,"info":0,"#,
+            r#""alignment":1,"entry_size":0}]}"#,
+        );
+        assert_eq!(document, expected);
+    }
+}
@@ -0,0 +1,14 @@
+/// One entry of a note section or segment
+pub struct Note {
+    pub name: String, // Owner, e.g. GNU, without the terminating NUL
+    pub note_type: u64,
+    pub descriptor: Vec<u8>, // Meaning depends on the owner and type
+}
+
+/// The notes of one SHT_NOTE section, or of one PT_NOTE segment if there are no sections
+pub struct NoteTable {
+    pub section_index: Option<usize>, // None when read from a PT_NOTE segment
+    pub offset: u64,
+    pub size: u64,
+    pub notes: Vec<Note>,
+}
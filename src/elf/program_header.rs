@@ -1,21 +1,414 @@
-#[derive(Debug)]
+use std::fmt::{Display, Formatter};
+use std::ops::BitOr;
+
+use crate::abi;
+use crate::elf::section_header::SectionHeader;
+
+/// Segment type from `p_type`. The OS- and processor-specific ranges are kept
+/// as raw values, since what they mean depends on the ABI and `e_machine`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum HeaderType {
     Null,
     Load,
     Dynamic,
     Interpreter,
     Note,
+    Shlib,
     ProgramHeaderTable,
+    Tls,
+    GnuEhFrame,
     GnuStack,
+    GnuRelro,
+    GnuProperty,
+    GnuSframe,
+    GnuMbind(u32),          // PT_GNU_MBIND_LO..=PT_GNU_MBIND_HI
+    OsSpecific(u32),        // PT_LOOS..=PT_HIOS
+    ProcessorSpecific(u32), // PT_LOPROC..=PT_HIPROC
+}
+
+impl HeaderType {
+    /// The `p_type` value this was decoded from
+    pub fn value(&self) -> u32 {
+        match *self {
+            HeaderType::Null => abi::PT_NULL,
+            HeaderType::Load => abi::PT_LOAD,
+            HeaderType::Dynamic => abi::PT_DYNAMIC,
+            HeaderType::Interpreter => abi::PT_INTERP,
+            HeaderType::Note => abi::PT_NOTE,
+            HeaderType::Shlib => abi::PT_SHLIB,
+            HeaderType::ProgramHeaderTable => abi::PT_PHDR,
+            HeaderType::Tls => abi::PT_TLS,
+            HeaderType::GnuEhFrame => abi::PT_GNU_EH_FRAME,
+            HeaderType::GnuStack => abi::PT_GNU_STACK,
+            HeaderType::GnuRelro => abi::PT_GNU_RELRO,
+            HeaderType::GnuProperty => abi::PT_GNU_PROPERTY,
+            HeaderType::GnuSframe => abi::PT_GNU_SFRAME,
+            HeaderType::GnuMbind(value)
+            | HeaderType::OsSpecific(value)
+            | HeaderType::ProcessorSpecific(value) => value,
+        }
+    }
+
+    /// Type name as GNU readelf shows it, which for processor-specific
+    /// segments depends on `e_machine`
+    pub fn name(&self, machine: u16) -> String {
+        let processor_name = match (machine, *self) {
+            (abi::EM_ARM, HeaderType::ProcessorSpecific(abi::PT_ARM_EXIDX)) => Some("EXIDX"),
+            (abi::EM_AARCH64, HeaderType::ProcessorSpecific(abi::PT_AARCH64_MEMTAG_MTE)) => {
+                Some("AARCH64_MEMTAG_MTE")
+            }
+            (abi::EM_RISCV, HeaderType::ProcessorSpecific(abi::PT_RISCV_ATTRIBUTES)) => {
+                Some("RISCV_ATTRIBUTES")
+            }
+            _ => None,
+        };
+        match processor_name {
+            Some(name) => name.to_string(),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl Display for HeaderType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HeaderType::Null => "NULL",
+            HeaderType::Load => "LOAD",
+            HeaderType::Dynamic => "DYNAMIC",
+            HeaderType::Interpreter => "INTERP",
+            HeaderType::Note => "NOTE",
+            HeaderType::Shlib => "SHLIB",
+            HeaderType::ProgramHeaderTable => "PHDR",
+            HeaderType::Tls => "TLS",
+            HeaderType::GnuEhFrame => "GNU_EH_FRAME",
+            HeaderType::GnuStack => "GNU_STACK",
+            HeaderType::GnuRelro => "GNU_RELRO",
+            HeaderType::GnuProperty => "GNU_PROPERTY",
+            HeaderType::GnuSframe => "GNU_SFRAME",
+            HeaderType::GnuMbind(value) => {
+                return write!(f, "GNU_MBIND+{:#x}", value - abi::PT_GNU_MBIND_LO);
+            }
+            HeaderType::OsSpecific(value) => {
+                return write!(f, "LOOS+{:#x}", value - abi::PT_LOOS);
+            }
+            HeaderType::ProcessorSpecific(value) => {
+                return write!(f, "LOPROC+{:#x}", value - abi::PT_LOPROC);
+            }
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Segment permissions from `p_flags`. Bits under PF_MASKOS and PF_MASKPROC are kept
+/// as they are, since only the ABI or `e_machine` says what they mean.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SegmentFlags(pub u32);
+
+impl SegmentFlags {
+    pub const READ: SegmentFlags = SegmentFlags(abi::PF_R);
+    pub const WRITE: SegmentFlags = SegmentFlags(abi::PF_W);
+    pub const EXECUTE: SegmentFlags = SegmentFlags(abi::PF_X);
+
+    /// True if every flag set in `other` is set here too
+    pub fn contains(&self, other: SegmentFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The PF_MASKOS bits
+    pub fn os_specific(&self) -> u32 {
+        self.0 & abi::PF_MASKOS
+    }
+
+    /// The PF_MASKPROC bits
+    pub fn processor_specific(&self) -> u32 {
+        self.0 & abi::PF_MASKPROC
+    }
+}
+
+impl BitOr for SegmentFlags {
+    type Output = SegmentFlags;
+
+    fn bitor(self, rhs: SegmentFlags) -> SegmentFlags {
+        SegmentFlags(self.0 | rhs.0)
+    }
 }
 
+/// Three columns, `R`, `W` and `E`, blank where the flag isn't set, as GNU readelf
+/// shows them. Other bits aren't shown.
+impl Display for SegmentFlags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let flag = |flag, c| if self.contains(flag) { c } else { ' ' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(SegmentFlags::READ, 'R'),
+            flag(SegmentFlags::WRITE, 'W'),
+            flag(SegmentFlags::EXECUTE, 'E')
+        )
+    }
+}
+
+/// Program header, widened to hold either class. ELF64 moves `p_flags` up to
+/// second place, so field order here doesn't match either on-disk layout.
 pub struct ProgramHeader {
     pub header_type: HeaderType, // u32
-    pub offset: u32,
-    pub virtual_address: u32,
-    pub physical_address: u32,
-    pub size_in_file: u32,
-    pub size_in_memory: u32,
-    pub flags: u32,
-    pub alignment: u32, // TODO -- RWX bitflags
+    pub offset: u64,
+    pub virtual_address: u64,
+    pub physical_address: u64,
+    pub size_in_file: u64,
+    pub size_in_memory: u64,
+    pub flags: SegmentFlags, // u32
+    pub alignment: u64,
+}
+
+impl ProgramHeader {
+    /// Whether `address` falls in the memory this segment takes up, including the
+    /// zero-filled part past `size_in_file`
+    pub fn contains_address(&self, address: u64) -> bool {
+        address >= self.virtual_address && address - self.virtual_address < self.size_in_memory
+    }
+
+    /// File offset of the byte loaded at `address`. None if the segment doesn't load
+    /// it from the file.
+    pub fn address_to_offset(&self, address: u64) -> Option<u64> {
+        match address >= self.virtual_address && address - self.virtual_address < self.size_in_file
+        {
+            true => self.offset.checked_add(address - self.virtual_address),
+            false => None,
+        }
+    }
+
+    /// Virtual address the byte at file offset `offset` gets loaded at. None if the
+    /// segment doesn't load it.
+    pub fn offset_to_address(&self, offset: u64) -> Option<u64> {
+        match offset >= self.offset && offset - self.offset < self.size_in_file {
+            true => self.virtual_address.checked_add(offset - self.offset),
+            false => None,
+        }
+    }
+
+    /// A `.tbss` section only takes up address space in the PT_TLS segment. Every
+    /// other segment sees it as empty, since its bytes overlap what follows it.
+    fn is_tbss_special(&self, section: &SectionHeader) -> bool {
+        section.flags & abi::SHF_TLS as u64 != 0
+            && section.section_type == abi::SHT_NOBITS
+            && self.header_type != HeaderType::Tls
+    }
+
+    /// Whether the section lies inside this segment, by the same rules binutils
+    /// uses for readelf's section to segment mapping (`ELF_SECTION_IN_SEGMENT_STRICT`)
+    pub fn contains_section(&self, section: &SectionHeader) -> bool {
+        if self.is_tbss_special(section) {
+            return false;
+        }
+
+        let is_tls = section.flags & abi::SHF_TLS as u64 != 0;
+        let is_alloc = section.flags & abi::SHF_ALLOC as u64 != 0;
+        let is_nobits = section.section_type == abi::SHT_NOBITS;
+
+        // Only PT_LOAD, PT_GNU_RELRO and PT_TLS hold TLS sections, PT_TLS holds
+        // nothing else, and PT_PHDR holds no sections at all
+        let type_allowed = match is_tls {
+            true => matches!(
+                self.header_type,
+                HeaderType::Tls | HeaderType::GnuRelro | HeaderType::Load
+            ),
+            false => !matches!(
+                self.header_type,
+                HeaderType::Tls | HeaderType::ProgramHeaderTable
+            ),
+        };
+        // Segments that get mapped only hold sections that get mapped too
+        let alloc_allowed = is_alloc
+            || !matches!(
+                self.header_type,
+                HeaderType::Load
+                    | HeaderType::Dynamic
+                    | HeaderType::GnuEhFrame
+                    | HeaderType::GnuStack
+                    | HeaderType::GnuRelro
+                    | HeaderType::GnuSframe
+                    | HeaderType::GnuMbind(_)
+            );
+        if !type_allowed || !alloc_allowed {
+            return false;
+        }
+
+        // binutils does this math on unsigned values, wrapping included
+        let in_file = is_nobits
+            || (section.offset >= self.offset
+                && section.offset - self.offset <= self.size_in_file.wrapping_sub(1)
+                && (section.offset - self.offset).wrapping_add(section.size) <= self.size_in_file);
+        let in_memory = !is_alloc
+            || (section.address >= self.virtual_address
+                && section.address - self.virtual_address <= self.size_in_memory.wrapping_sub(1)
+                && (section.address - self.virtual_address).wrapping_add(section.size)
+                    <= self.size_in_memory);
+        if !in_file || !in_memory {
+            return false;
+        }
+
+        // Empty sections at the very start or end of PT_DYNAMIC and PT_NOTE don't count
+        if !matches!(self.header_type, HeaderType::Dynamic | HeaderType::Note)
+            || section.size != 0
+            || self.size_in_memory == 0
+        {
+            return true;
+        }
+        let strictly_in_file = is_nobits
+            || (section.offset > self.offset && section.offset - self.offset < self.size_in_file);
+        let strictly_in_memory = !is_alloc
+            || (section.address > self.virtual_address
+                && section.address - self.virtual_address < self.size_in_memory);
+        strictly_in_file && strictly_in_memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(
+        header_type: HeaderType,
+        address: u64,
+        size_in_file: u64,
+        size: u64,
+    ) -> ProgramHeader {
+        ProgramHeader {
+            header_type,
+            offset: address - 0x400000, // One page in for every address below
+            virtual_address: address,
+            physical_address: address,
+            size_in_file,
+            size_in_memory: size,
+            flags: SegmentFlags::READ,
+            alignment: 1,
+        }
+    }
+
+    fn section(section_type: u32, flags: u32, address: u64, size: u64) -> SectionHeader {
+        SectionHeader {
+            name_offset: 0,
+            name: String::new(),
+            section_type,
+            flags: (abi::SHF_ALLOC | flags) as u64,
+            address,
+            offset: address - 0x400000,
+            size,
+            link: 0,
+            info: 0,
+            alignment: 1,
+            entry_size: 0,
+        }
+    }
+
+    #[test]
+    fn tbss_is_only_in_pt_tls() {
+        let load = segment(HeaderType::Load, 0x401000, 0x100, 0x100);
+        let tls = segment(HeaderType::Tls, 0x401100, 0, 0x10);
+        let tbss = section(
+            abi::SHT_NOBITS,
+            abi::SHF_TLS | abi::SHF_WRITE,
+            0x401100,
+            0x10,
+        );
+        assert!(!load.contains_section(&tbss));
+        assert!(tls.contains_section(&tbss));
+
+        // Not even where its addresses fall inside the PT_LOAD
+        let tbss = section(
+            abi::SHT_NOBITS,
+            abi::SHF_TLS | abi::SHF_WRITE,
+            0x4010f0,
+            0x10,
+        );
+        assert!(!load.contains_section(&tbss));
+    }
+
+    #[test]
+    fn empty_section_at_a_boundary() {
+        let load = segment(HeaderType::Load, 0x401000, 0x100, 0x100);
+        assert!(load.contains_section(&section(abi::SHT_PROGBITS, 0, 0x401000, 0)));
+        assert!(!load.contains_section(&section(abi::SHT_PROGBITS, 0, 0x401100, 0)));
+
+        // PT_DYNAMIC and PT_NOTE don't count one at their start either
+        for header_type in [HeaderType::Dynamic, HeaderType::Note] {
+            let ph = segment(header_type, 0x401000, 0x100, 0x100);
+            assert!(!ph.contains_section(&section(abi::SHT_PROGBITS, 0, 0x401000, 0)));
+            assert!(ph.contains_section(&section(abi::SHT_PROGBITS, 0, 0x401010, 0)));
+            assert!(ph.contains_section(&section(abi::SHT_PROGBITS, 0, 0x401000, 0x10)));
+        }
+    }
+
+    #[test]
+    fn nobits_past_the_file_size() {
+        let load = segment(HeaderType::Load, 0x401000, 0x100, 0x200);
+        let bss = section(abi::SHT_NOBITS, abi::SHF_WRITE, 0x401100, 0x100);
+        assert!(load.contains_section(&bss));
+
+        // Only NOBITS may take up memory without file bytes behind it
+        let data = section(abi::SHT_PROGBITS, abi::SHF_WRITE, 0x401100, 0x100);
+        assert!(!load.contains_section(&data));
+        let too_big = section(abi::SHT_NOBITS, abi::SHF_WRITE, 0x401100, 0x101);
+        assert!(!load.contains_section(&too_big));
+    }
+
+    #[test]
+    fn processor_specific_names() {
+        let attributes = HeaderType::ProcessorSpecific(abi::PT_RISCV_ATTRIBUTES);
+        assert_eq!(attributes.name(abi::EM_RISCV), "RISCV_ATTRIBUTES");
+        assert_eq!(attributes.name(abi::EM_X86_64), "LOPROC+0x3");
+    }
+
+    #[test]
+    fn gnu_segment_names() {
+        assert_eq!(HeaderType::GnuSframe.to_string(), "GNU_SFRAME");
+        let mbind = HeaderType::GnuMbind(abi::PT_GNU_MBIND_LO + 0x10);
+        assert_eq!(mbind.name(abi::EM_X86_64), "GNU_MBIND+0x10");
+        assert_eq!(mbind.value(), 0x6474e565);
+    }
+
+    #[test]
+    fn mapped_gnu_segments_hold_only_alloc_sections() {
+        let mut comment = section(abi::SHT_PROGBITS, 0, 0x401000, 0x10);
+        comment.flags = 0;
+        let text = section(abi::SHT_PROGBITS, abi::SHF_EXECINSTR, 0x401000, 0x10);
+        for header_type in [
+            HeaderType::GnuSframe,
+            HeaderType::GnuMbind(abi::PT_GNU_MBIND_LO),
+            HeaderType::GnuMbind(abi::PT_GNU_MBIND_HI),
+        ] {
+            let ph = segment(header_type, 0x401000, 0x100, 0x100);
+            assert!(ph.contains_section(&text), "{header_type}");
+            assert!(!ph.contains_section(&comment), "{header_type}");
+        }
+
+        // Just past the PT_GNU_MBIND range, which has no such rule
+        let os = segment(
+            HeaderType::OsSpecific(abi::PT_GNU_MBIND_HI + 1),
+            0x401000,
+            0x100,
+            0x100,
+        );
+        assert!(os.contains_section(&comment));
+    }
+
+    #[test]
+    fn segment_flags() {
+        assert_eq!(SegmentFlags::default().to_string(), "   ");
+        let read = SegmentFlags::READ;
+        assert_eq!(read.to_string(), "R  ");
+        assert_eq!((read | SegmentFlags::EXECUTE).to_string(), "R E");
+        let all = read | SegmentFlags::WRITE | SegmentFlags::EXECUTE;
+        assert_eq!(all.to_string(), "RWE");
+
+        // OS- and processor-specific bits are kept, but not shown
+        let masked = SegmentFlags(abi::PF_R | 0x00100000 | 0x10000000);
+        assert_eq!(masked.to_string(), "R  ");
+        assert_eq!(
+            (masked.os_specific(), masked.processor_specific()),
+            (0x00100000, 0x10000000)
+        );
+        assert_eq!(SegmentFlags(0x8).to_string(), "   "); // Between PF_R and PF_MASKOS
+    }
 }
@@ -0,0 +1,14 @@
+/// One SHT_REL or SHT_RELA entry, widened to hold either class
+pub struct Relocation {
+    pub offset: u64,
+    pub info: u64,            // r_info as stored, Word in ELF32
+    pub symbol_index: u32,    // Upper bits of r_info
+    pub relocation_type: u32, // Lower bits of r_info, interpreted per e_machine
+    pub addend: Option<i64>,  // Only SHT_RELA entries carry one
+}
+
+/// The relocations of one SHT_REL or SHT_RELA section
+pub struct RelocationTable {
+    pub section_index: usize,
+    pub relocations: Vec<Relocation>,
+}
@@ -0,0 +1,30 @@
+use crate::to_str;
+
+/// Section header, widened to hold either class
+pub struct SectionHeader {
+    pub name_offset: u32,  // sh_name, index into .shstrtab
+    pub name: String,      // Resolved from sh_name
+    pub section_type: u32, // SHT_*
+    pub flags: u64,        // SHF_*, Word in ELF32
+    pub address: u64,
+    pub offset: u64,
+    pub size: u64, // Word in ELF32
+    pub link: u32,
+    pub info: u32,
+    pub alignment: u64,  // Word in ELF32
+    pub entry_size: u64, // Word in ELF32
+}
+
+impl SectionHeader {
+    /// Type name as GNU readelf shows it, e.g. PROGBITS
+    pub fn type_name(&self) -> String {
+        match to_str::sh_type_to_str(self.section_type) {
+            // readelf drops the GNU_ prefix for the symbol versioning sections only
+            Some(name @ ("SHT_GNU_VERDEF" | "SHT_GNU_VERNEED" | "SHT_GNU_VERSYM")) => {
+                name.trim_start_matches("SHT_GNU_").to_string()
+            }
+            Some(name) => name.trim_start_matches("SHT_").to_string(),
+            None => format!("{:08x}: <unknown>", self.section_type),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::abi;
+use crate::to_str;
+
+/// A dynamic symbol to look up, as `name`, `name@VERSION` or `name@@VERSION`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SymbolLookup {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl SymbolLookup {
+    /// `@@` marks the default version when defining a symbol, but either form asks
+    /// for that version when looking one up
+    pub fn parse(lookup: &str) -> SymbolLookup {
+        match lookup.split_once('@') {
+            Some((name, version)) => SymbolLookup {
+                name: name.to_string(),
+                version: Some(version.trim_start_matches('@').to_string()),
+            },
+            None => SymbolLookup {
+                name: lookup.to_string(),
+                version: None,
+            },
+        }
+    }
+}
+
+/// Symbol table entry, widened to hold either class
+pub struct Symbol {
+    pub name: String, // Resolved from st_name through the linked string table
+    pub value: u64,
+    pub size: u64,          // Word in ELF32
+    pub info: u8,           // Type in the low nibble, binding in the high one
+    pub other: u8,          // Visibility in the low two bits
+    pub shndx: u16,         // st_shndx as stored, which may be SHN_XINDEX
+    pub section_index: u32, // Real section index, from SHT_SYMTAB_SHNDX when escaped
+}
+
+impl Symbol {
+    pub fn symbol_type(&self) -> u8 {
+        self.info & 0xf
+    }
+
+    pub fn binding(&self) -> u8 {
+        self.info >> 4
+    }
+
+    pub fn visibility(&self) -> u8 {
+        self.other & 0x3
+    }
+
+    /// Type name as GNU readelf shows it, e.g. FUNC
+    pub fn type_name(&self) -> String {
+        match to_str::st_symtype_to_str(self.symbol_type()) {
+            Some(name) => name
+                .trim_start_matches("STT_GNU_")
+                .trim_start_matches("STT_")
+                .to_string(),
+            None => format!("<unknown>: {}", self.symbol_type()),
+        }
+    }
+
+    /// Binding name as GNU readelf shows it, e.g. GLOBAL
+    pub fn binding_name(&self) -> String {
+        match to_str::st_bind_to_str(self.binding()) {
+            Some(name) => name
+                .trim_start_matches("STB_GNU_")
+                .trim_start_matches("STB_")
+                .to_string(),
+            None => format!("<unknown>: {}", self.binding()),
+        }
+    }
+
+    /// Visibility name as GNU readelf shows it, e.g. DEFAULT
+    pub fn visibility_name(&self) -> String {
+        match to_str::st_vis_to_str(self.visibility()) {
+            Some(name) => name.trim_start_matches("STV_").to_string(),
+            None => format!("<unknown>: {}", self.visibility()),
+        }
+    }
+
+    /// Section index as GNU readelf shows it, with the reserved indexes spelled out
+    pub fn section_index_name(&self) -> String {
+        match self.shndx {
+            abi::SHN_UNDEF => "UND".to_string(),
+            abi::SHN_ABS => "ABS".to_string(),
+            abi::SHN_COMMON => "COM".to_string(),
+            _ => self.section_index.to_string(),
+        }
+    }
+}
+
+/// The symbols of one SHT_SYMTAB or SHT_DYNSYM section
+pub struct SymbolTable {
+    pub section_index: usize,
+    pub symbols: Vec<Symbol>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str, version: Option<&str>) -> SymbolLookup {
+        SymbolLookup {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parse_symbol_lookup() {
+        assert_eq!(SymbolLookup::parse("main"), lookup("main", None));
+        assert_eq!(
+            SymbolLookup::parse("memcpy@GLIBC_2.14"),
+            lookup("memcpy", Some("GLIBC_2.14"))
+        );
+        assert_eq!(
+            SymbolLookup::parse("memcpy@@GLIBC_2.14"),
+            lookup("memcpy", Some("GLIBC_2.14"))
+        );
+    }
+}
@@ -0,0 +1,550 @@
+//! ELF files built in memory for unit tests: PT_LOAD segments laid out one after the
+//! other after the program headers, then any sections, then `.shstrtab` and the section
+//! header table.
+
+use crate::abi;
+use crate::elf::hash::gnu_hash;
+use crate::elf::header::{Class, Endianness};
+
+/// Fields written in a file's class and byte order
+pub struct Writer {
+    pub buffer: Vec<u8>,
+    class: Class,
+    endianness: Endianness,
+}
+
+impl Writer {
+    pub fn new(class: Class, endianness: Endianness) -> Writer {
+        Writer {
+            buffer: Vec::new(),
+            class,
+            endianness,
+        }
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        match self.endianness {
+            Endianness::Little => self.buffer.extend(value.to_le_bytes()),
+            Endianness::Big => self.buffer.extend(value.to_be_bytes()),
+        }
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        match self.endianness {
+            Endianness::Little => self.buffer.extend(value.to_le_bytes()),
+            Endianness::Big => self.buffer.extend(value.to_be_bytes()),
+        }
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        match self.endianness {
+            Endianness::Little => self.buffer.extend(value.to_le_bytes()),
+            Endianness::Big => self.buffer.extend(value.to_be_bytes()),
+        }
+    }
+
+    /// A field that is 4 bytes in ELF32 and 8 bytes in ELF64
+    pub fn class_sized(&mut self, value: u64) {
+        match self.class {
+            Class::Elf32 => self.u32(value as u32),
+            Class::Elf64 => self.u64(value),
+        }
+    }
+}
+
+/// A PT_LOAD segment to build
+pub struct Segment {
+    pub physical_address: u64,
+    pub virtual_address: u64,
+    pub data: Vec<u8>,
+    pub size_in_memory: u64,
+}
+
+impl Segment {
+    /// Loaded at `address`, both physical and virtual, with no zero-filled tail
+    pub fn new(address: u64, data: &[u8]) -> Segment {
+        Segment {
+            physical_address: address,
+            virtual_address: address,
+            data: data.to_vec(),
+            size_in_memory: data.len() as u64,
+        }
+    }
+}
+
+/// A section to build. Section 0 is the null section, so `sections[i]` ends up at
+/// index `i + 1`, with `.shstrtab` after the last one.
+pub struct Section {
+    pub name: &'static str,
+    pub section_type: u32,
+    pub flags: u64,
+    pub address: u64,
+    pub link: u32,
+    pub info: u32,
+    pub entry_size: u64,
+    pub data: Vec<u8>,
+}
+
+impl Section {
+    pub fn new(name: &'static str, section_type: u32, data: Vec<u8>) -> Section {
+        Section {
+            name,
+            section_type,
+            flags: 0,
+            address: 0,
+            link: 0,
+            info: 0,
+            entry_size: 0,
+            data,
+        }
+    }
+}
+
+/// An ELF64 little-endian executable with only PT_LOAD segments
+pub fn build_elf(entry: u64, segments: &[Segment]) -> Vec<u8> {
+    build_elf_with(Class::Elf64, Endianness::Little, entry, segments, &[])
+}
+
+pub fn build_elf_with(
+    class: Class,
+    endianness: Endianness,
+    entry: u64,
+    segments: &[Segment],
+    sections: &[Section],
+) -> Vec<u8> {
+    let header_size = class.elf_header_size();
+    let program_header_size = class.program_header_size();
+    let section_header_size = class.section_header_size();
+
+    let mut string_table = vec![0];
+    let mut name_offsets = Vec::new();
+    for name in sections
+        .iter()
+        .map(|section| section.name)
+        .chain([".shstrtab"])
+    {
+        name_offsets.push(string_table.len() as u32);
+        string_table.extend(name.as_bytes());
+        string_table.push(0);
+    }
+
+    let data_offset = header_size + program_header_size * segments.len();
+    let segment_data_size: usize = segments.iter().map(|segment| segment.data.len()).sum();
+    let section_data_offset = data_offset + segment_data_size;
+    let section_data_size: usize = sections.iter().map(|section| section.data.len()).sum();
+    let string_table_offset = section_data_offset + section_data_size;
+    let section_header_offset = match sections.is_empty() {
+        true => 0,
+        false => string_table_offset + string_table.len(),
+    };
+    let section_count = match sections.is_empty() {
+        true => 0,
+        false => sections.len() + 2,
+    };
+
+    let mut writer = Writer::new(class, endianness);
+    let class_byte = match class {
+        Class::Elf32 => abi::ELFCLASS32,
+        Class::Elf64 => abi::ELFCLASS64,
+    };
+    let data_byte = match endianness {
+        Endianness::Little => abi::ELFDATA2LSB,
+        Endianness::Big => abi::ELFDATA2MSB,
+    };
+    writer.buffer = vec![
+        0x7f,
+        b'E',
+        b'L',
+        b'F',
+        class_byte,
+        data_byte,
+        abi::EV_CURRENT,
+    ];
+    writer.buffer.resize(abi::EI_NIDENT, 0);
+    writer.u16(abi::ET_EXEC);
+    writer.u16(abi::EM_X86_64);
+    writer.u32(1); // e_version
+    writer.class_sized(entry);
+    writer.class_sized(header_size as u64); // e_phoff
+    writer.class_sized(section_header_offset as u64);
+    writer.u32(0); // e_flags
+    writer.u16(header_size as u16);
+    writer.u16(program_header_size as u16);
+    writer.u16(segments.len() as u16);
+    writer.u16(section_header_size as u16);
+    writer.u16(section_count as u16);
+    writer.u16(section_count.saturating_sub(1) as u16); // e_shstrndx, the last section
+
+    let mut offset = data_offset;
+    for segment in segments {
+        writer.u32(abi::PT_LOAD);
+        let flags = abi::PF_R | abi::PF_X;
+        if class == Class::Elf64 {
+            writer.u32(flags);
+        }
+        writer.class_sized(offset as u64);
+        writer.class_sized(segment.virtual_address);
+        writer.class_sized(segment.physical_address);
+        writer.class_sized(segment.data.len() as u64);
+        writer.class_sized(segment.size_in_memory);
+        if class == Class::Elf32 {
+            writer.u32(flags);
+        }
+        writer.class_sized(1); // p_align
+        offset += segment.data.len();
+    }
+    for segment in segments {
+        writer.buffer.extend(&segment.data);
+    }
+    if sections.is_empty() {
+        return writer.buffer;
+    }
+    for section in sections {
+        writer.buffer.extend(&section.data);
+    }
+    writer.buffer.extend(&string_table);
+
+    writer
+        .buffer
+        .resize(writer.buffer.len() + section_header_size, 0); // Section 0
+    let mut offset = section_data_offset;
+    for (section, &name_offset) in sections.iter().zip(&name_offsets) {
+        writer.u32(name_offset);
+        writer.u32(section.section_type);
+        writer.class_sized(section.flags);
+        writer.class_sized(section.address);
+        writer.class_sized(offset as u64);
+        writer.class_sized(section.data.len() as u64);
+        writer.u32(section.link);
+        writer.u32(section.info);
+        writer.class_sized(1); // sh_addralign
+        writer.class_sized(section.entry_size);
+        offset += section.data.len();
+    }
+    writer.u32(*name_offsets.last().unwrap());
+    writer.u32(abi::SHT_STRTAB);
+    writer.class_sized(0); // sh_flags
+    writer.class_sized(0); // sh_addr
+    writer.class_sized(string_table_offset as u64);
+    writer.class_sized(string_table.len() as u64);
+    writer.buffer.extend([0; 8]); // sh_link, sh_info
+    writer.class_sized(1); // sh_addralign
+    writer.class_sized(0); // sh_entsize
+    writer.buffer
+}
+
+/// A dynamic symbol for `build_versioned_elf`, with its .gnu.version entry
+struct VersionedSymbol {
+    name: &'static str,
+    shndx: u16,
+    version: u16,
+}
+
+/// The symbols of `build_versioned_elf`, one for each way a version can be shown
+const VERSIONED_SYMBOLS: [VersionedSymbol; 7] = [
+    VersionedSymbol {
+        name: "",
+        shndx: abi::SHN_UNDEF,
+        version: abi::VER_NDX_LOCAL,
+    },
+    // @@LIBX_2.0, the default version
+    VersionedSymbol {
+        name: "foo",
+        shndx: 1,
+        version: 2,
+    },
+    // @LIBX_2.0, hidden
+    VersionedSymbol {
+        name: "bar",
+        shndx: 1,
+        version: abi::VER_NDX_HIDDEN | 2,
+    },
+    // @GLIBC_2.34 (3), needed
+    VersionedSymbol {
+        name: "memcpy",
+        shndx: abi::SHN_UNDEF,
+        version: 3,
+    },
+    // No suffix, as it's the absolute symbol for the version itself
+    VersionedSymbol {
+        name: "LIBX_2.0",
+        shndx: abi::SHN_ABS,
+        version: 2,
+    },
+    // No suffix, as the base definition only names the file
+    VersionedSymbol {
+        name: "baz",
+        shndx: 1,
+        version: abi::VER_NDX_GLOBAL,
+    },
+    // No suffix, as a hidden global isn't looked up among the definitions
+    VersionedSymbol {
+        name: "qux",
+        shndx: 1,
+        version: abi::VER_NDX_HIDDEN_GLOBAL,
+    },
+];
+
+/// An ELF64 little-endian shared object with `.dynstr`, `.dynsym`, `.gnu.version_d`,
+/// `.gnu.version_r` and `.gnu.version`, at sections 1 to 5. libx.so defines LIBX_1.0
+/// and LIBX_2.0, and needs GLIBC_2.34 (3) and GLIBC_2.2.5 (4) from libc.so.6.
+///
+/// Every definition and need declares one more entry than it links to. The second
+/// definition inherits from a name out of range, and the second need names a file out
+/// of range.
+pub fn build_versioned_elf() -> Vec<u8> {
+    let mut strings = vec![0];
+    let mut string = |string: &str| {
+        let offset = strings.len() as u32;
+        strings.extend(string.as_bytes());
+        strings.push(0);
+        offset
+    };
+    let symbol_names: Vec<u32> = VERSIONED_SYMBOLS
+        .iter()
+        .map(|symbol| string(symbol.name))
+        .collect();
+    let (libx, libx_2_0) = (string("libx.so"), string("LIBX_2.0"));
+    let (libc, glibc_2_34, glibc_2_2_5) = (
+        string("libc.so.6"),
+        string("GLIBC_2.34"),
+        string("GLIBC_2.2.5"),
+    );
+
+    let mut symbols = Writer::new(Class::Elf64, Endianness::Little);
+    for (symbol, &name) in VERSIONED_SYMBOLS.iter().zip(&symbol_names) {
+        symbols.u32(name);
+        symbols.u8((abi::STB_GLOBAL << 4) | abi::STT_FUNC);
+        symbols.u8(0); // st_other
+        symbols.u16(symbol.shndx);
+        symbols.u64(0); // st_value
+        symbols.u64(0); // st_size
+    }
+    let mut symbols = Section::new(".dynsym", abi::SHT_DYNSYM, symbols.buffer);
+    symbols.link = 1;
+    symbols.entry_size = 24;
+
+    let mut definitions = Writer::new(Class::Elf64, Endianness::Little);
+    let mut definition = |index: u16, flags: u16, names: &[u32], next: u32| {
+        definitions.u16(abi::VER_DEF_CURRENT);
+        definitions.u16(flags);
+        definitions.u16(index);
+        definitions.u16(names.len() as u16 + 1); // vd_cnt, one more than linked
+        definitions.u32(0); // vd_hash
+        definitions.u32(20); // vd_aux
+        definitions.u32(next);
+        for (name_idx, &name) in names.iter().enumerate() {
+            definitions.u32(name);
+            let is_last = name_idx + 1 == names.len();
+            definitions.u32(if is_last { 0 } else { 8 });
+        }
+    };
+    definition(abi::VER_NDX_GLOBAL, abi::VER_FLG_BASE, &[libx], 28);
+    definition(2, 0, &[libx_2_0, 0x1000], 0);
+    let mut definitions = Section::new(".gnu.version_d", abi::SHT_GNU_VERDEF, definitions.buffer);
+    definitions.link = 1;
+    definitions.info = 2;
+
+    let mut needs = Writer::new(Class::Elf64, Endianness::Little);
+    let mut need = |file: u32, requirements: &[(u16, u32)], next: u32| {
+        needs.u16(abi::VER_NEED_CURRENT);
+        needs.u16(requirements.len() as u16 + 1); // vn_cnt, one more than linked
+        needs.u32(file);
+        needs.u32(16); // vn_aux
+        needs.u32(next);
+        for (requirement_idx, &(index, name)) in requirements.iter().enumerate() {
+            needs.u32(0); // vna_hash
+            needs.u16(0); // vna_flags
+            needs.u16(index);
+            needs.u32(name);
+            let is_last = requirement_idx + 1 == requirements.len();
+            needs.u32(if is_last { 0 } else { 16 });
+        }
+    };
+    need(libc, &[(3, glibc_2_34), (4, glibc_2_2_5)], 48);
+    need(0x2000, &[(5, glibc_2_2_5)], 0);
+    let mut needs = Section::new(".gnu.version_r", abi::SHT_GNU_VERNEED, needs.buffer);
+    needs.link = 1;
+    needs.info = 2;
+
+    let mut versions = Writer::new(Class::Elf64, Endianness::Little);
+    for symbol in VERSIONED_SYMBOLS.iter() {
+        versions.u16(symbol.version);
+    }
+    let mut versions = Section::new(".gnu.version", abi::SHT_GNU_VERSYM, versions.buffer);
+    versions.link = 2;
+
+    build_elf_with(
+        Class::Elf64,
+        Endianness::Little,
+        0,
+        &[],
+        &[
+            Section::new(".dynstr", abi::SHT_STRTAB, strings),
+            symbols,
+            definitions,
+            needs,
+            versions,
+        ],
+    )
+}
+
+/// A relocation to build: r_offset, symbol index, type and, for SHT_RELA, r_addend
+pub type TestRelocation = (u64, u32, u32, i64);
+
+/// `.rel.dyn` entries, all against `counter`
+pub const RELS: [TestRelocation; 1] = [(0x404000, 1, abi::R_X86_64_64, 0)];
+
+/// `.rela.text` entries: one against `counter` and one with no symbol
+pub const RELAS: [TestRelocation; 2] = [
+    (0x404008, 1, abi::R_X86_64_PC32, -4),
+    (0x404018, 0, abi::R_X86_64_RELATIVE, 0x401000),
+];
+
+/// A file with `.strtab` (1), `.symtab` (2) holding `counter` at 0x404010, `.rel.dyn` (3)
+/// and `.rela.text` (4), both linked to `.symtab`
+pub fn build_relocation_elf(class: Class, endianness: Endianness) -> Vec<u8> {
+    let writer = || Writer::new(class, endianness);
+
+    let mut symbols = writer();
+    for (name, value) in [(0, 0), (1, 0x404010)] {
+        symbols.u32(name);
+        match class {
+            Class::Elf32 => {
+                symbols.u32(value as u32);
+                symbols.u32(8); // st_size
+                symbols.u8((abi::STB_GLOBAL << 4) | abi::STT_OBJECT);
+                symbols.u8(0); // st_other
+                symbols.u16(abi::SHN_UNDEF);
+            }
+            Class::Elf64 => {
+                symbols.u8((abi::STB_GLOBAL << 4) | abi::STT_OBJECT);
+                symbols.u8(0); // st_other
+                symbols.u16(abi::SHN_UNDEF);
+                symbols.u64(value);
+                symbols.u64(8); // st_size
+            }
+        }
+    }
+    let mut symbols = Section::new(".symtab", abi::SHT_SYMTAB, symbols.buffer);
+    symbols.link = 1;
+
+    let table = |name, section_type, relocations: &[TestRelocation]| {
+        let mut entries = writer();
+        for &(offset, symbol, relocation_type, addend) in relocations {
+            entries.class_sized(offset);
+            match class {
+                Class::Elf32 => entries.u32((symbol << 8) | relocation_type),
+                Class::Elf64 => entries.u64(((symbol as u64) << 32) | relocation_type as u64),
+            }
+            if section_type == abi::SHT_RELA {
+                entries.class_sized(addend as u64);
+            }
+        }
+        let mut section = Section::new(name, section_type, entries.buffer);
+        section.link = 2;
+        section
+    };
+
+    let (symbol_size, rel_size, rela_size) = match class {
+        Class::Elf32 => (16, 8, 12),
+        Class::Elf64 => (24, 16, 24),
+    };
+    symbols.entry_size = symbol_size;
+    let mut rels = table(".rel.dyn", abi::SHT_REL, &RELS);
+    rels.entry_size = rel_size;
+    let mut relas = table(".rela.text", abi::SHT_RELA, &RELAS);
+    relas.entry_size = rela_size;
+
+    build_elf_with(
+        class,
+        endianness,
+        0,
+        &[],
+        &[
+            Section::new(".strtab", abi::SHT_STRTAB, b"\0counter\0".to_vec()),
+            symbols,
+            rels,
+            relas,
+        ],
+    )
+}
+
+/// Defined functions in `.dynsym` from index 1 on, each valued 0x401000 plus its index
+pub const HASHED_SYMBOLS: [&str; 2] = ["foo", "bar"];
+
+/// Bloom filter shift of the `.gnu.hash` table in [`build_hashed_elf`]
+pub const BLOOM_SHIFT: u32 = 6;
+
+/// An ELF64 little-endian file with `.dynstr` (1), `.dynsym` (2) holding
+/// [`HASHED_SYMBOLS`], and both a `.hash` (3) and a `.gnu.hash` (4) table over them,
+/// each with a single bucket. The bloom filter has only their bits set.
+pub fn build_hashed_elf() -> Vec<u8> {
+    let mut strings = vec![0];
+    let mut symbols = Writer::new(Class::Elf64, Endianness::Little);
+    symbols.buffer.resize(24, 0); // The null symbol
+    for (idx, name) in HASHED_SYMBOLS.iter().enumerate() {
+        symbols.u32(strings.len() as u32);
+        strings.extend(name.as_bytes());
+        strings.push(0);
+        symbols.u8((abi::STB_GLOBAL << 4) | abi::STT_FUNC);
+        symbols.u8(0); // st_other
+        symbols.u16(1); // st_shndx
+        symbols.u64(0x401000 + idx as u64 + 1);
+        symbols.u64(0); // st_size
+    }
+    let mut symbols = Section::new(".dynsym", abi::SHT_DYNSYM, symbols.buffer);
+    symbols.link = 1;
+    symbols.entry_size = 24;
+
+    let symbol_count = HASHED_SYMBOLS.len() as u32 + 1;
+    let mut hash = Writer::new(Class::Elf64, Endianness::Little);
+    hash.u32(1); // nbucket
+    hash.u32(symbol_count); // nchain
+    hash.u32(symbol_count - 1); // The chain runs from the last symbol down
+    hash.u32(0); // Chain entry for the null symbol
+    for idx in 1..symbol_count {
+        hash.u32(idx - 1);
+    }
+    let mut hash = Section::new(".hash", abi::SHT_HASH, hash.buffer);
+    hash.link = 2;
+    hash.entry_size = 4;
+
+    let hashes: Vec<u32> = HASHED_SYMBOLS
+        .iter()
+        .map(|name| gnu_hash(name.as_bytes()))
+        .collect();
+    let bloom = hashes.iter().fold(0u64, |bloom, &hash| {
+        bloom | 1 << (hash % 64) | 1 << ((hash >> BLOOM_SHIFT) % 64)
+    });
+    let mut gnu_hash_table = Writer::new(Class::Elf64, Endianness::Little);
+    gnu_hash_table.u32(1); // nbuckets
+    gnu_hash_table.u32(1); // symoffset
+    gnu_hash_table.u32(1); // bloom_size
+    gnu_hash_table.u32(BLOOM_SHIFT);
+    gnu_hash_table.u64(bloom);
+    gnu_hash_table.u32(1); // The only bucket starts at the first symbol
+    for (idx, hash) in hashes.iter().enumerate() {
+        let is_last = idx + 1 == hashes.len();
+        gnu_hash_table.u32(if is_last { hash | 1 } else { hash & !1 });
+    }
+    let mut gnu_hash_table = Section::new(".gnu.hash", abi::SHT_GNU_HASH, gnu_hash_table.buffer);
+    gnu_hash_table.link = 2;
+
+    build_elf_with(
+        Class::Elf64,
+        Endianness::Little,
+        0,
+        &[],
+        &[
+            Section::new(".dynstr", abi::SHT_STRTAB, strings),
+            symbols,
+            hash,
+            gnu_hash_table,
+        ],
+    )
+}
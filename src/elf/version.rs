@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter};
+
+/// A name attached to a version definition, from one Elf_Verdaux
+pub struct VersionDefinitionName {
+    pub offset: u64, // Within the section, as readelf shows it
+    pub name: String,
+}
+
+/// One Elf_Verdef entry of .gnu.version_d
+pub struct VersionDefinition {
+    pub offset: u64, // Within the section, as readelf shows it
+    pub version: u16,
+    pub flags: u16, // VER_FLG_*
+    pub index: u16, // vd_ndx, which .gnu.version entries refer to
+    /// The version's own name, then the names of the versions it inherits from
+    pub names: Vec<VersionDefinitionName>,
+}
+
+/// One Elf_Vernaux entry, a version wanted from a needed file
+pub struct VersionRequirement {
+    pub offset: u64, // Within the section, as readelf shows it
+    pub flags: u16,  // VER_FLG_*
+    pub index: u16,  // vna_other, which .gnu.version entries refer to
+    pub name: String,
+}
+
+/// One Elf_Verneed entry of .gnu.version_r, naming a file and the versions needed from it
+pub struct VersionNeed {
+    pub offset: u64, // Within the section, as readelf shows it
+    pub version: u16,
+    pub file: String,
+    pub requirements: Vec<VersionRequirement>,
+}
+
+/// The SHT_GNU_VERDEF section
+pub struct VersionDefinitionTable {
+    pub section_index: usize,
+    pub definitions: Vec<VersionDefinition>,
+}
+
+/// The SHT_GNU_VERNEED section
+pub struct VersionNeedTable {
+    pub section_index: usize,
+    pub needs: Vec<VersionNeed>,
+}
+
+/// The SHT_GNU_VERSYM section, with one entry per .dynsym symbol
+pub struct VersionSymbolTable {
+    pub section_index: usize,
+    pub versions: Vec<u16>, // Index into the definitions or requirements, plus VER_NDX_HIDDEN
+}
+
+/// A version name split into its family and release numbers, so that GLIBC_2.17 orders
+/// after GLIBC_2.2.5. Names without numbers, like GLIBC_PRIVATE, have no order.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct VersionNumber {
+    pub family: String,
+    pub numbers: Vec<u32>,
+}
+
+impl VersionNumber {
+    pub fn parse(name: &str) -> Option<Self> {
+        let (family, numbers) = name.rsplit_once('_')?;
+        let numbers = numbers
+            .split('.')
+            .map(|number| number.parse().ok())
+            .collect::<Option<Vec<u32>>>()?;
+        Some(Self {
+            family: family.to_string(),
+            numbers,
+        })
+    }
+}
+
+impl Display for VersionNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let numbers: Vec<String> = self.numbers.iter().map(u32::to_string).collect();
+        write!(f, "{}_{}", self.family, numbers.join("."))
+    }
+}
+
+/// A library version required that's newer than the baseline allows
+#[derive(Debug, Eq, PartialEq)]
+pub struct TooNewVersion<'a> {
+    pub file: &'a str,
+    pub version: VersionNumber,
+    pub baseline: &'a VersionNumber,
+}
+
+impl Display for TooNewVersion<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} needs {}, newer than the {} baseline",
+            self.file, self.version, self.baseline
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(name: &str) -> VersionNumber {
+        VersionNumber::parse(name).unwrap()
+    }
+
+    #[test]
+    fn numbers_order_numerically() {
+        assert_eq!(version("GLIBC_2.2.5").numbers, [2, 2, 5]);
+        assert!(version("GLIBC_2.17") > version("GLIBC_2.2.5"));
+        assert!(version("GLIBC_2.3") > version("GLIBC_2.2.5"));
+        assert!(version("GLIBC_2.2.5") > version("GLIBC_2.2"));
+        assert_eq!(version("GLIBC_2.17").to_string(), "GLIBC_2.17");
+    }
+
+    #[test]
+    fn family_is_everything_before_the_last_underscore() {
+        assert_eq!(version("GLIBC_2.17").family, "GLIBC");
+        assert_eq!(version("GCC_3.0").family, "GCC");
+        assert_eq!(version("CXXABI_ARM_1.3.3").family, "CXXABI_ARM");
+    }
+
+    #[test]
+    fn names_without_numbers() {
+        for name in [
+            "GLIBC_PRIVATE",
+            "GLIBC_",
+            "GLIBC_2.x",
+            "GLIBC_2..5",
+            "libc.so.6",
+        ] {
+            assert_eq!(VersionNumber::parse(name), None, "{name}");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! What the fuzz target runs on each input, shared with `tests/fuzz_corpus.rs` so the
+//! corpus replays exactly what was fuzzed. Only built with the `fuzzing` feature, as
+//! it's not part of the API.
+
+use crate::elf::image::{FlatBinaryOptions, LoadAddress};
+use crate::{parse_elf_with, Elf, Validation};
+
+/// Large enough for any corpus input, small enough that fuzzing doesn't spend its
+/// time filling gaps
+const MAX_IMAGE_SIZE: u64 = 1 << 20;
+
+/// Parse `data` and decode everything that parsed. Any step may return an error, but
+/// none may panic.
+pub fn exercise(data: &[u8]) {
+    // Lenient reads on past more problems, so it reaches more of the decoders
+    let Ok(elf) = parse_elf_with(data, Validation::Lenient) else {
+        return;
+    };
+    let _ = elf.header.to_string();
+    let dumps = [
+        Elf::file_header_json,
+        Elf::program_headers_json,
+        Elf::section_headers_json,
+        Elf::symbol_tables_json,
+        Elf::relocation_tables_json,
+        Elf::dynamic_json,
+        Elf::version_info_json,
+        Elf::histograms_json,
+        Elf::notes_json,
+        Elf::minimum_library_versions_json,
+    ];
+    for dump in dumps {
+        let _ = dump(&elf).to_string();
+    }
+    let _ = elf.symbol_lookup_json("main", None).to_string();
+    let _ = elf
+        .symbol_lookup_json("main", Some("GLIBC_2.2.5"))
+        .to_string();
+
+    if let Some(offset) = elf.virtual_address_to_offset(elf.header.entry) {
+        let _ = elf.offset_to_virtual_address(offset);
+    }
+    let _ = elf.read_virtual(data, elf.header.entry, 64);
+
+    for load_address in [LoadAddress::Physical, LoadAddress::Virtual] {
+        let options = FlatBinaryOptions {
+            load_address,
+            gap_fill: 0xff,
+            start_address: Some(elf.header.entry),
+            pad_to: Some(0x200),
+            max_size: MAX_IMAGE_SIZE,
+        };
+        let _ = elf.flat_binary(data, &options);
+        let _ = elf.check_boot_sector(data, load_address);
+        let _ = elf.boot_sector_with_signature(data, load_address);
+        let _ = elf.intel_hex(data, load_address);
+        let _ = elf.srec(data, load_address, "fuzz");
+    }
+}
@@ -0,0 +1,29 @@
+//! Parse ELF files into plain structs, and print them the way GNU readelf does.
+//!
+//! [`parse_elf`] reads the whole file up front into an [`Elf`], which holds the
+//! header, program and section headers, and every table we know how to decode.
+//! Nothing borrows the input, so the buffer can be dropped once it's parsed.
+//!
+//! ```no_run
+//! let buffer = std::fs::read("/bin/ls").unwrap();
+//! let elf = mark_readelf::parse_elf(&buffer).unwrap();
+//! println!("entry point {:#x}", elf.header.entry);
+//! for ph in elf.program_header_table.iter() {
+//!     println!("{} at {:#x}", ph.header_type, ph.virtual_address);
+//! }
+//! ```
+//!
+//! [`Parser`] is the integer reader underneath, for reading structures we don't decode.
+
+pub mod abi;
+pub mod elf;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+pub mod json;
+pub mod parse;
+pub mod to_str;
+
+pub use crate::elf::header::ElfHeader;
+pub use crate::elf::program_header::ProgramHeader;
+pub use crate::elf::Elf;
+pub use crate::parse::{parse_elf, parse_elf_with, ParseError, Parser, Validation};
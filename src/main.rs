@@ -1,17 +1,246 @@
-use std::error::Error;
+use std::env;
 use std::fs;
+use std::process::ExitCode;
 
-mod abi;
-mod elf;
-mod parse;
-mod to_str;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let file_path = "/Users/boathouse/Projects/questions/static_vars/target/i686-unknown-none/debug/static_vars";
-    let buffer = fs::read(file_path)?;
-    let elf = parse::parse_elf(&buffer)?;
-    elf.print_elf_header();
-    println!();
-    elf.print_program_header_table(false);
+use anyhow::{bail, Context, Result};
+
+use mark_readelf::elf::boot_sector::BootSectorProblem;
+use mark_readelf::{elf, parse_elf, parse_elf_with, Elf, Validation};
+
+use crate::cli::{BinaryOptions, BootSectorOptions, Command, HexOptions, Options};
+
+mod cli;
+
+fn dump_file(file_path: &str, options: &Options) -> Result<()> {
+    let buffer = fs::read(file_path).context("could not read file")?;
+    let validation = match options.lenient {
+        true => Validation::Lenient,
+        false => Validation::Strict,
+    };
+    let elf = parse_elf_with(&buffer, validation)?;
+    for warning in elf.warnings.iter() {
+        eprintln!("mark-readelf: Warning: '{file_path}': {warning}");
+    }
+
+    match options.json {
+        true => println!(
+            "{}",
+            elf::json::document(&elf, file_path, &options.selection)
+        ),
+        false => dump_text(&elf, options),
+    }
+    check_version_baselines(&elf, options)
+}
+
+fn dump_text(elf: &Elf, options: &Options) {
+    if options.selection.file_header {
+        elf.print_elf_header();
+        println!();
+    }
+    if options.selection.program_headers {
+        // readelf only repeats the file type and entry point if the file header wasn't shown
+        elf.print_program_header_table(!options.selection.file_header, options.wide);
+        println!();
+    }
+    if options.selection.section_headers {
+        elf.print_section_header_table(!options.selection.file_header);
+        println!();
+    }
+    if options.selection.symbols {
+        elf.print_symbol_tables();
+        println!();
+    }
+    if options.selection.relocations {
+        elf.print_relocation_tables();
+        println!();
+    }
+    if options.selection.dynamic {
+        elf.print_dynamic_section();
+        println!();
+    }
+    if options.selection.version_info {
+        elf.print_version_info();
+        println!();
+    }
+    if options.selection.histogram {
+        elf.print_histograms();
+        println!();
+    }
+    if options.selection.notes {
+        elf.print_notes();
+        println!();
+    }
+    for lookup in options.selection.lookups.iter() {
+        elf.print_symbol_lookup(&lookup.name, lookup.version.as_deref());
+        println!();
+    }
+    if options.selection.required_versions {
+        elf.print_minimum_library_versions();
+        println!();
+    }
+}
+
+/// Checked after the dumps, so they're still shown when a requirement is too new
+fn check_version_baselines(elf: &Elf, options: &Options) -> Result<()> {
+    let too_new = elf.versions_newer_than(&options.version_baselines);
+    if !too_new.is_empty() {
+        let too_new: Vec<String> = too_new.iter().map(ToString::to_string).collect();
+        bail!("{}", too_new.join("; "));
+    }
     Ok(())
 }
+
+/// `binary`: the loadable segments as one flat image, written to `output_path`
+fn write_binary(elf_path: &str, output_path: &str, options: &BinaryOptions) -> Result<()> {
+    let buffer = fs::read(elf_path).context("could not read file")?;
+    let elf = parse_elf(&buffer)?;
+    let image = elf.flat_binary(&buffer, &options.image)?;
+    fs::write(output_path, image).with_context(|| format!("could not write '{output_path}'"))
+}
+
+fn run_binary(options: &BinaryOptions) -> ExitCode {
+    if options.help {
+        println!("{}", cli::BINARY_USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let [elf_path, output_path] = options.files.as_slice() else {
+        eprintln!("{}", cli::BINARY_USAGE);
+        return ExitCode::FAILURE;
+    };
+    match write_binary(elf_path, output_path, options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("mark-readelf: Error: '{elf_path}': {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `boot-sector`: what would keep the file from booting, after writing the sector with
+/// the signature added if asked to
+fn check_boot_sector(
+    elf_path: &str,
+    options: &BootSectorOptions,
+) -> Result<Vec<BootSectorProblem>> {
+    let buffer = fs::read(elf_path).context("could not read file")?;
+    let elf = parse_elf(&buffer)?;
+    let mut problems = elf.check_boot_sector(&buffer, options.load_address)?;
+
+    if let Some(output_path) = &options.add_signature {
+        let sector = match elf.boot_sector_with_signature(&buffer, options.load_address) {
+            Ok(sector) => sector,
+            Err(err) => {
+                // The problems say what takes up the room, so they go out with the error
+                print_boot_sector_problems(elf_path, &problems);
+                return Err(err).context("no room to add the boot signature");
+            }
+        };
+        fs::write(output_path, sector)
+            .with_context(|| format!("could not write '{output_path}'"))?;
+        problems.retain(|problem| !matches!(problem, BootSectorProblem::MissingSignature(_)));
+    }
+    Ok(problems)
+}
+
+fn print_boot_sector_problems(elf_path: &str, problems: &[BootSectorProblem]) {
+    for problem in problems {
+        eprintln!("mark-readelf: Error: '{elf_path}': {problem}");
+    }
+}
+
+/// `ihex` and `srec`: the loadable segments as records for a flash programmer
+fn write_hex(elf_path: &str, output_path: &str, srec: bool, options: &HexOptions) -> Result<()> {
+    let buffer = fs::read(elf_path).context("could not read file")?;
+    let elf = parse_elf(&buffer)?;
+    let records = match srec {
+        // objcopy names the S-records after the file they're written to
+        true => elf.srec(&buffer, options.load_address, output_path)?,
+        false => elf.intel_hex(&buffer, options.load_address)?,
+    };
+    fs::write(output_path, records).with_context(|| format!("could not write '{output_path}'"))
+}
+
+fn run_hex(options: &HexOptions, srec: bool) -> ExitCode {
+    if options.help {
+        println!("{}", cli::HEX_USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let [elf_path, output_path] = options.files.as_slice() else {
+        eprintln!("{}", cli::HEX_USAGE);
+        return ExitCode::FAILURE;
+    };
+    match write_hex(elf_path, output_path, srec, options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("mark-readelf: Error: '{elf_path}': {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_boot_sector(options: &BootSectorOptions) -> ExitCode {
+    if options.help {
+        println!("{}", cli::BOOT_SECTOR_USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let [elf_path] = options.files.as_slice() else {
+        eprintln!("{}", cli::BOOT_SECTOR_USAGE);
+        return ExitCode::FAILURE;
+    };
+    match check_boot_sector(elf_path, options) {
+        Ok(problems) if problems.is_empty() => ExitCode::SUCCESS,
+        Ok(problems) => {
+            print_boot_sector_problems(elf_path, &problems);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("mark-readelf: Error: '{elf_path}': {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_dump(options: &Options) -> ExitCode {
+    if options.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+    if options.files.is_empty() || !options.selection.any() {
+        if !options.files.is_empty() {
+            eprintln!("mark-readelf: Warning: Nothing to do.");
+        }
+        eprintln!("{}", cli::USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for file_path in options.files.iter() {
+        if options.files.len() > 1 && !options.json {
+            println!();
+            println!("File: {file_path}");
+        }
+        if let Err(err) = dump_file(file_path, options) {
+            eprintln!("mark-readelf: Error: '{file_path}': {err:#}");
+            status = ExitCode::FAILURE;
+        }
+    }
+    status
+}
+
+fn main() -> ExitCode {
+    let command = match cli::parse_command(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("mark-readelf: {err:#}");
+            eprintln!("{}", cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match command {
+        Command::Dump(options) => run_dump(&options),
+        Command::Binary(options) => run_binary(&options),
+        Command::BootSector(options) => run_boot_sector(&options),
+        Command::IntelHex(options) => run_hex(&options, false),
+        Command::Srec(options) => run_hex(&options, true),
+    }
+}
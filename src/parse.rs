@@ -1,10 +1,23 @@
-//! Copied from https://github.com/cole14/rust-elf/tree/master
+//! Copied from <https://github.com/cole14/rust-elf/tree/master>
 
 use crate::abi;
-use crate::elf::header::{ElfHeader, FileType, Machine, OsAbi};
-use crate::elf::program_header::{HeaderType, ProgramHeader};
+use crate::elf::address::virtual_address_to_offset;
+use crate::elf::dynamic::{DynamicEntry, DynamicSection};
+use crate::elf::hash::{GnuHashTable, HashTable};
+use crate::elf::header::{Class, ElfHeader, Endianness, FileType, Machine, OsAbi};
+use crate::elf::note::{Note, NoteTable};
+use crate::elf::program_header::{HeaderType, ProgramHeader, SegmentFlags};
+use crate::elf::relocation::{Relocation, RelocationTable};
+use crate::elf::section_header::SectionHeader;
+use crate::elf::symbol::{Symbol, SymbolTable};
+use crate::elf::version::{
+    VersionDefinition, VersionDefinitionName, VersionDefinitionTable, VersionNeed,
+    VersionNeedTable, VersionRequirement, VersionSymbolTable,
+};
 use crate::elf::Elf;
 
+/// Why a file couldn't be parsed. Not every case is reachable from the parts of ELF we
+/// handle yet.
 #[derive(Debug)]
 pub enum ParseError {
     /// Returned when the ELF File Header's magic bytes weren't ELF's defined
@@ -37,6 +50,9 @@ pub enum ParseError {
     /// Returned when trying to interpret a segment's data as the wrong type.
     /// For example, trying to treat an PT_LOAD section as a PT_NOTE.
     UnexpectedSegmentType((u32, u32)),
+    /// Returned when a program header's `p_type` is outside the OS- and
+    /// processor-specific ranges and isn't one of the defined `PT_*` constants
+    UnsupportedSegmentType(u32),
     /// Returned when a section has a sh_addralign value that was different
     /// than we expected.
     UnexpectedAlignment(usize),
@@ -46,6 +62,9 @@ pub enum ParseError {
     SliceReadError((usize, usize)),
     /// Returned when doing math with parsed elf fields that resulted in integer overflow.
     IntegerOverflow,
+    /// Returned when a GNU hash bucket, given first, names a symbol below the table's
+    /// symbol offset, given second
+    BadHashBucket((u32, u32)),
     /// Returned when parsing a string out of a StringTable that contained
     /// invalid Utf8
     Utf8Error(core::str::Utf8Error),
@@ -74,9 +93,11 @@ impl std::error::Error for ParseError {
             ParseError::BadEntsize(_) => None,
             ParseError::UnexpectedSectionType(_) => None,
             ParseError::UnexpectedSegmentType(_) => None,
+            ParseError::UnsupportedSegmentType(_) => None,
             ParseError::UnexpectedAlignment(_) => None,
             ParseError::SliceReadError(_) => None,
             ParseError::IntegerOverflow => None,
+            ParseError::BadHashBucket(_) => None,
             ParseError::Utf8Error(ref err) => Some(err),
             ParseError::TryFromSliceError(ref err) => Some(err),
             ParseError::TryFromIntError(ref err) => Some(err),
@@ -133,6 +154,9 @@ impl core::fmt::Display for ParseError {
                     "Could not interpret section of type {found} as type {expected}"
                 )
             }
+            ParseError::UnsupportedSegmentType(found) => {
+                write!(f, "Unsupported program header type {found:#x}")
+            }
             ParseError::UnexpectedAlignment(align) => {
                 write!(
                     f,
@@ -145,6 +169,12 @@ impl core::fmt::Display for ParseError {
             ParseError::IntegerOverflow => {
                 write!(f, "Integer overflow detected")
             }
+            ParseError::BadHashBucket((bucket, symbol_offset)) => {
+                write!(
+                    f,
+                    "Corrupt GNU hash table: bucket {bucket} is below the symbol offset {symbol_offset}"
+                )
+            }
             ParseError::Utf8Error(ref err) => err.fmt(f),
             ParseError::TryFromSliceError(ref err) => err.fmt(f),
             ParseError::TryFromIntError(ref err) => err.fmt(f),
@@ -177,87 +207,142 @@ impl From<std::io::Error> for ParseError {
     }
 }
 
-/// Parse ints from a little-endian byte array
+/// Parse ints from a byte array, in the byte order given by the file's data encoding.
+/// Address-sized fields are read at the width given by the file's class.
 pub struct Parser<'buffer> {
     offset: usize,
     buffer: &'buffer [u8],
+    class: Class,
+    endianness: Endianness,
 }
 
 impl<'buffer> Parser<'buffer> {
-    pub fn new(buffer: &'buffer [u8]) -> Self {
-        Self::new_with_offset(buffer, 0)
+    /// Read `buffer` from the start
+    pub fn new(buffer: &'buffer [u8], class: Class, endianness: Endianness) -> Self {
+        Self::new_with_offset(buffer, 0, class, endianness)
     }
 
-    pub fn new_with_offset(buffer: &'buffer [u8], offset: usize) -> Self {
-        Self { offset, buffer }
+    /// Read `buffer` from `offset` on
+    pub fn new_with_offset(
+        buffer: &'buffer [u8],
+        offset: usize,
+        class: Class,
+        endianness: Endianness,
+    ) -> Self {
+        Self {
+            offset,
+            buffer,
+            class,
+            endianness,
+        }
     }
 
-    pub fn parse_u8(&mut self) -> Result<u8, ParseError> {
+    /// Take the next `N` bytes, unconverted
+    fn parse_bytes<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
         let start = self.offset;
-        let end = self.offset + 1;
-        let value = self
+        let end = checked_end(start, N)?;
+        let slice: &[u8] = self
             .buffer
-            .get(start)
-            .cloned()
+            .get(start..end)
             .ok_or(ParseError::SliceReadError((start, end)))?;
+        let bytes = slice.try_into()?;
         self.offset = end;
+        Ok(bytes)
+    }
+
+    pub fn parse_u8(&mut self) -> Result<u8, ParseError> {
+        let [value] = self.parse_bytes()?;
         Ok(value)
     }
 
     pub fn parse_u16(&mut self) -> Result<u16, ParseError> {
-        let start = self.offset;
-        let end = self.offset + 2;
-        let slice: &[u8] = self
-            .buffer
-            .get(start..end)
-            .ok_or(ParseError::SliceReadError((start, end)))?;
-        let value = u16::from_le_bytes(slice.try_into()?);
-        self.offset = end;
-        Ok(value)
+        let bytes = self.parse_bytes()?;
+        match self.endianness {
+            Endianness::Little => Ok(u16::from_le_bytes(bytes)),
+            Endianness::Big => Ok(u16::from_be_bytes(bytes)),
+        }
     }
 
     pub fn parse_u32(&mut self) -> Result<u32, ParseError> {
-        let start = self.offset;
-        let end = self.offset + 4;
-        let slice: &[u8] = self
-            .buffer
-            .get(start..end)
-            .ok_or(ParseError::SliceReadError((start, end)))?;
-        let value = u32::from_le_bytes(slice.try_into()?);
-        self.offset = end;
-        Ok(value)
+        let bytes = self.parse_bytes()?;
+        match self.endianness {
+            Endianness::Little => Ok(u32::from_le_bytes(bytes)),
+            Endianness::Big => Ok(u32::from_be_bytes(bytes)),
+        }
+    }
+
+    pub fn parse_u64(&mut self) -> Result<u64, ParseError> {
+        let bytes = self.parse_bytes()?;
+        match self.endianness {
+            Endianness::Little => Ok(u64::from_le_bytes(bytes)),
+            Endianness::Big => Ok(u64::from_be_bytes(bytes)),
+        }
+    }
+
+    /// Parse a field that is 4 bytes in ELF32 and 8 bytes in ELF64,
+    /// like Elf_Addr, Elf_Off, or the Word/Xword pairs used for sizes and flags
+    pub fn parse_class_sized(&mut self) -> Result<u64, ParseError> {
+        match self.class {
+            Class::Elf32 => Ok(self.parse_u32()? as u64),
+            Class::Elf64 => self.parse_u64(),
+        }
+    }
+
+    /// Parse an Elf_Off as a usize, for indexing into the file
+    pub fn parse_offset(&mut self) -> Result<usize, ParseError> {
+        let offset = self.parse_class_sized()?;
+        Ok(usize::try_from(offset)?)
+    }
+
+    /// Where the next read starts, from the start of the buffer
+    pub fn offset(&self) -> usize {
+        self.offset
     }
 
     pub fn skip_u8(&mut self) {
-        self.offset += 1;
+        self.offset = self.offset.saturating_add(1); // Out of range fails the next read
     }
 
     pub fn skip_u16(&mut self) {
-        self.offset += 2;
+        self.offset = self.offset.saturating_add(2);
     }
 
     pub fn skip_u32(&mut self) {
-        self.offset += 4;
+        self.offset = self.offset.saturating_add(4);
     }
 }
 
+/// `start + length`, the end of a range of file bytes
+fn checked_end(start: usize, length: usize) -> Result<usize, ParseError> {
+    start.checked_add(length).ok_or(ParseError::IntegerOverflow)
+}
+
+/// The `length` bytes at `start`, for offsets and sizes read from the file
+pub(crate) fn file_range(buffer: &[u8], start: u64, length: u64) -> Result<&[u8], ParseError> {
+    let start = usize::try_from(start)?;
+    let end = checked_end(start, usize::try_from(length)?)?;
+    buffer
+        .get(start..end)
+        .ok_or(ParseError::SliceReadError((start, end)))
+}
+
 /// Verify identification bytes at start of ELF file
 pub fn verify_e_ident(buffer: &[u8]) -> Result<(), ParseError> {
-    let magic = buffer.split_at(abi::EI_CLASS).0; // Header has e_ident bytes, then EI_CLASS
+    let buffer: &[u8; abi::EI_NIDENT] = buffer
+        .get(..abi::EI_NIDENT)
+        .ok_or(ParseError::SliceReadError((0, abi::EI_NIDENT)))?
+        .try_into()?;
+    let magic: [u8; 4] = buffer[..abi::EI_CLASS].try_into()?; // e_ident bytes, then EI_CLASS
     if magic != abi::ELFMAGIC {
-        return Err(ParseError::BadMagic([
-            magic[0], magic[1], magic[2], magic[3],
-        ]));
+        return Err(ParseError::BadMagic(magic));
     }
 
-    // We care only for ELF32,
-    // little endian
     let class = buffer[abi::EI_CLASS];
-    if class != abi::ELFCLASS32 {
+    if class != abi::ELFCLASS32 && class != abi::ELFCLASS64 {
         return Err(ParseError::UnsupportedElfClass(class));
     }
     let endianness = buffer[abi::EI_DATA];
-    if endianness != abi::ELFDATA2LSB {
+    if endianness != abi::ELFDATA2LSB && endianness != abi::ELFDATA2MSB {
         return Err(ParseError::UnsupportedElfEndianness(endianness));
     }
 
@@ -274,62 +359,109 @@ pub fn verify_e_ident(buffer: &[u8]) -> Result<(), ParseError> {
 }
 
 /// Parse the interesting data from e_ident. We care about:
+/// - CLASS
+/// - DATA
 /// - OSABI
 /// - ABIVERSION
-pub fn parse_e_ident(buffer: &[u8]) -> Result<(OsAbi, u8), ParseError> {
-    verify_e_ident(buffer)?;
+pub fn parse_e_ident(buffer: &[u8]) -> Result<(Class, Endianness, OsAbi, u8), ParseError> {
+    verify_e_ident(buffer)?; // Checks the length too
+    let class = match buffer[abi::EI_CLASS] {
+        abi::ELFCLASS32 => Class::Elf32,
+        _ => Class::Elf64,
+    };
+    let endianness = match buffer[abi::EI_DATA] {
+        abi::ELFDATA2LSB => Endianness::Little,
+        _ => Endianness::Big,
+    };
     let os_abi = buffer[abi::EI_OSABI];
     let abi_version = buffer[abi::EI_ABIVERSION];
-    Ok((OsAbi(os_abi), abi_version))
+    Ok((class, endianness, OsAbi(os_abi), abi_version))
 }
 
 pub fn parse_elf_header(buffer: &[u8]) -> Result<ElfHeader, ParseError> {
-    let (os_abi, abi_version) = parse_e_ident(&buffer[..abi::EI_NIDENT])?;
+    let (class, endianness, os_abi, abi_version) = parse_e_ident(buffer)?;
+    let ident = buffer[..abi::EI_NIDENT].try_into()?;
 
-    let mut parser = Parser::new(&buffer[abi::EI_NIDENT..]);
+    let mut parser = Parser::new_with_offset(buffer, abi::EI_NIDENT, class, endianness);
 
     let file_type = parser.parse_u16()?;
     let file_type = match file_type {
-        0 => Ok(FileType::None),
-        1 => Ok(FileType::Rel),
-        2 => Ok(FileType::Exec),
-        3 => Ok(FileType::Dyn),
-        4 => Ok(FileType::Core),
+        abi::ET_NONE => Ok(FileType::None),
+        abi::ET_REL => Ok(FileType::Rel),
+        abi::ET_EXEC => Ok(FileType::Exec),
+        abi::ET_DYN => Ok(FileType::Dyn),
+        abi::ET_CORE => Ok(FileType::Core),
+        abi::ET_LOOS..=abi::ET_HIOS => Ok(FileType::OsSpecific(file_type)),
+        abi::ET_LOPROC..=abi::ET_HIPROC => Ok(FileType::ProcessorSpecific(file_type)),
         file_type => Err(ParseError::UnsupportedFileType(file_type)),
     }?;
     let machine = parser.parse_u16()?;
     let machine = Machine(machine);
-    parser.skip_u32(); // e_version, already checked
-    let entry = parser.parse_u32()?;
-    let program_header_offset = parser.parse_u32()? as usize;
-    let section_header_offset = parser.parse_u32()? as usize;
-    parser.skip_u32(); // flags: u32, always 0
+    let version = parser.parse_u32()?;
+    let entry = parser.parse_class_sized()?;
+    let program_header_offset = parser.parse_offset()?;
+    let section_header_offset = parser.parse_offset()?;
+    let flags = parser.parse_u32()?;
     let elf_header_size = parser.parse_u16()? as usize;
     let program_header_entry_size = parser.parse_u16()? as usize;
-    let program_header_entries = parser.parse_u16()? as usize;
+    let stored_program_header_entries = parser.parse_u16()?;
     let section_header_entry_size = parser.parse_u16()? as usize;
-    let section_header_entries = parser.parse_u16()? as usize;
-    let string_table_index = parser.parse_u16()? as usize;
+    let stored_section_header_entries = parser.parse_u16()?;
+    let stored_string_table_index = parser.parse_u16()?;
+
+    // Counts too big for their field are escaped, and kept in section 0 instead
+    let mut program_header_entries = stored_program_header_entries as usize;
+    let mut section_header_entries = stored_section_header_entries as usize;
+    let mut string_table_index = stored_string_table_index as usize;
+    let is_extended = stored_program_header_entries == abi::PN_XNUM
+        || stored_section_header_entries == 0
+        || stored_string_table_index == abi::SHN_XINDEX;
+    if section_header_offset != 0 && is_extended {
+        let section_0 = parse_section_header(buffer, section_header_offset, class, endianness)?;
+        if stored_program_header_entries == abi::PN_XNUM && section_0.info != 0 {
+            program_header_entries = section_0.info as usize;
+        }
+        if stored_section_header_entries == 0 {
+            section_header_entries = usize::try_from(section_0.size)?;
+        }
+        if stored_string_table_index == abi::SHN_XINDEX {
+            string_table_index = section_0.link as usize;
+        }
+    }
 
     Ok(ElfHeader {
+        ident,
+        class,
+        endianness,
         os_abi,
         abi_version,
         file_type,
         machine,
+        version,
         entry,
         program_header_offset,
         section_header_offset,
+        flags,
         elf_header_size,
         program_header_entry_size,
         program_header_entries,
         section_header_entry_size,
         section_header_entries,
         string_table_index,
+        stored_program_header_entries,
+        stored_section_header_entries,
+        stored_string_table_index,
+        is_pie: false, // Needs the dynamic section, which parse_elf reads later
     })
 }
 
-fn parse_program_header(buffer: &[u8], offset: usize) -> Result<ProgramHeader, ParseError> {
-    let mut parser = Parser::new_with_offset(buffer, offset);
+fn parse_program_header(
+    buffer: &[u8],
+    offset: usize,
+    class: Class,
+    endianness: Endianness,
+) -> Result<ProgramHeader, ParseError> {
+    let mut parser = Parser::new_with_offset(buffer, offset, class, endianness);
     let header_type = parser.parse_u32()?;
     let header_type = match header_type {
         abi::PT_NULL => Ok(HeaderType::Null),
@@ -337,17 +469,33 @@ fn parse_program_header(buffer: &[u8], offset: usize) -> Result<ProgramHeader, P
         abi::PT_DYNAMIC => Ok(HeaderType::Dynamic),
         abi::PT_INTERP => Ok(HeaderType::Interpreter),
         abi::PT_NOTE => Ok(HeaderType::Note),
+        abi::PT_SHLIB => Ok(HeaderType::Shlib),
         abi::PT_PHDR => Ok(HeaderType::ProgramHeaderTable),
+        abi::PT_TLS => Ok(HeaderType::Tls),
+        abi::PT_GNU_EH_FRAME => Ok(HeaderType::GnuEhFrame),
         abi::PT_GNU_STACK => Ok(HeaderType::GnuStack),
-        _ => Err(ParseError::UnexpectedSegmentType((0, 0))),
+        abi::PT_GNU_RELRO => Ok(HeaderType::GnuRelro),
+        abi::PT_GNU_PROPERTY => Ok(HeaderType::GnuProperty),
+        abi::PT_GNU_SFRAME => Ok(HeaderType::GnuSframe),
+        abi::PT_GNU_MBIND_LO..=abi::PT_GNU_MBIND_HI => Ok(HeaderType::GnuMbind(header_type)),
+        abi::PT_LOOS..=abi::PT_HIOS => Ok(HeaderType::OsSpecific(header_type)),
+        abi::PT_LOPROC..=abi::PT_HIPROC => Ok(HeaderType::ProcessorSpecific(header_type)),
+        _ => Err(ParseError::UnsupportedSegmentType(header_type)),
     }?;
-    let offset = parser.parse_u32()?;
-    let virtual_address = parser.parse_u32()?;
-    let physical_address = parser.parse_u32()?;
-    let size_in_file = parser.parse_u32()?;
-    let size_in_memory = parser.parse_u32()?;
-    let flags = parser.parse_u32()?;
-    let alignment = parser.parse_u32()?;
+    // ELF64 puts p_flags right after p_type, to keep the 8-byte fields aligned
+    let mut flags = match class {
+        Class::Elf32 => 0,
+        Class::Elf64 => parser.parse_u32()?,
+    };
+    let offset = parser.parse_class_sized()?;
+    let virtual_address = parser.parse_class_sized()?;
+    let physical_address = parser.parse_class_sized()?;
+    let size_in_file = parser.parse_class_sized()?;
+    let size_in_memory = parser.parse_class_sized()?;
+    if class == Class::Elf32 {
+        flags = parser.parse_u32()?;
+    }
+    let alignment = parser.parse_class_sized()?;
 
     Ok(ProgramHeader {
         header_type,
@@ -356,38 +504,1558 @@ fn parse_program_header(buffer: &[u8], offset: usize) -> Result<ProgramHeader, P
         physical_address,
         size_in_file,
         size_in_memory,
-        flags,
+        flags: SegmentFlags(flags),
         alignment,
     })
 }
 
+/// Reject a table of `entries` that can't fit in the file before reading any of it.
+/// Counts come from the file, and can be as large as section 0's `sh_size`.
+fn check_table_fits(
+    buffer: &[u8],
+    offset: usize,
+    entry_size: usize,
+    expected_entry_size: usize,
+    entries: usize,
+) -> Result<(), ParseError> {
+    if entries == 0 {
+        return Ok(());
+    }
+    if entry_size == 0 {
+        return Err(ParseError::BadEntsize((0, expected_entry_size as u64)));
+    }
+    if entries > buffer.len().saturating_sub(offset) / entry_size {
+        let end = entry_size.saturating_mul(entries).saturating_add(offset);
+        return Err(ParseError::SliceReadError((offset, end)));
+    }
+    Ok(())
+}
+
 pub fn parse_program_header_table(
     buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
     offset: usize,
     entry_size: usize,
     entries: usize,
 ) -> Result<Vec<ProgramHeader>, ParseError> {
+    check_table_fits(
+        buffer,
+        offset,
+        entry_size,
+        class.program_header_size(),
+        entries,
+    )?;
     let mut result = Vec::new();
     for entry_idx in 0..entries {
-        let ph_offset = offset + entry_size * entry_idx;
-        let ph = parse_program_header(buffer, ph_offset)?;
+        let ph_offset = entry_size
+            .checked_mul(entry_idx)
+            .and_then(|entry_offset| entry_offset.checked_add(offset))
+            .ok_or(ParseError::IntegerOverflow)?;
+        let ph = parse_program_header(buffer, ph_offset, class, endianness)?;
         result.push(ph);
     }
     Ok(result)
 }
 
+fn parse_section_header(
+    buffer: &[u8],
+    offset: usize,
+    class: Class,
+    endianness: Endianness,
+) -> Result<SectionHeader, ParseError> {
+    let mut parser = Parser::new_with_offset(buffer, offset, class, endianness);
+    let name_offset = parser.parse_u32()?;
+    let section_type = parser.parse_u32()?;
+    let flags = parser.parse_class_sized()?;
+    let address = parser.parse_class_sized()?;
+    let offset = parser.parse_class_sized()?;
+    let size = parser.parse_class_sized()?;
+    let link = parser.parse_u32()?;
+    let info = parser.parse_u32()?;
+    let alignment = parser.parse_class_sized()?;
+    let entry_size = parser.parse_class_sized()?;
+
+    Ok(SectionHeader {
+        name_offset,
+        name: String::new(), // Resolved once we have .shstrtab
+        section_type,
+        flags,
+        address,
+        offset,
+        size,
+        link,
+        info,
+        alignment,
+        entry_size,
+    })
+}
+
+pub fn parse_section_header_table(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    offset: usize,
+    entry_size: usize,
+    entries: usize,
+) -> Result<Vec<SectionHeader>, ParseError> {
+    check_table_fits(
+        buffer,
+        offset,
+        entry_size,
+        class.section_header_size(),
+        entries,
+    )?;
+    let mut result = Vec::new();
+    for entry_idx in 0..entries {
+        let sh_offset = entry_size
+            .checked_mul(entry_idx)
+            .and_then(|entry_offset| entry_offset.checked_add(offset))
+            .ok_or(ParseError::IntegerOverflow)?;
+        let sh = parse_section_header(buffer, sh_offset, class, endianness)?;
+        result.push(sh);
+    }
+    Ok(result)
+}
+
+/// The bytes of a section in the file. SHT_NOBITS sections have none.
+pub fn section_data<'buffer>(
+    buffer: &'buffer [u8],
+    section: &SectionHeader,
+) -> Result<&'buffer [u8], ParseError> {
+    if section.section_type == abi::SHT_NOBITS {
+        return Ok(&[]);
+    }
+    file_range(buffer, section.offset, section.size)
+}
+
+/// Read the NUL-terminated string starting `index` bytes into the string table `table`
+pub fn parse_string<'buffer>(
+    buffer: &'buffer [u8],
+    table: &SectionHeader,
+    index: usize,
+) -> Result<&'buffer str, ParseError> {
+    let table_data = section_data(buffer, table)?;
+    parse_string_in(table_data, index)
+}
+
+/// Read the NUL-terminated string starting `index` bytes into `table_data`
+fn parse_string_in(table_data: &[u8], index: usize) -> Result<&str, ParseError> {
+    let string_data = table_data
+        .get(index..)
+        .ok_or(ParseError::BadOffset(index as u64))?;
+    let string_end = string_data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or(ParseError::StringTableMissingNul(index as u64))?;
+    Ok(std::str::from_utf8(&string_data[..string_end])?)
+}
+
+/// What readelf shows in place of a name it can't read
+pub const CORRUPT_NAME: &str = "<corrupt>";
+
+/// The string at `index`, or `CORRUPT_NAME` with a warning if it can't be read
+fn parse_name(table_data: &[u8], index: usize, warnings: &mut Vec<ParseError>) -> String {
+    match parse_string_in(table_data, index) {
+        Ok(name) => name.to_string(),
+        Err(error) => {
+            warnings.push(error);
+            CORRUPT_NAME.to_string()
+        }
+    }
+}
+
+fn parse_symbol(parser: &mut Parser, class: Class) -> Result<(u32, Symbol), ParseError> {
+    let name_offset = parser.parse_u32()?;
+    let (value, size, info, other, shndx) = match class {
+        Class::Elf32 => {
+            let value = parser.parse_class_sized()?;
+            let size = parser.parse_class_sized()?;
+            let info = parser.parse_u8()?;
+            let other = parser.parse_u8()?;
+            let shndx = parser.parse_u16()?;
+            (value, size, info, other, shndx)
+        }
+        Class::Elf64 => {
+            let info = parser.parse_u8()?;
+            let other = parser.parse_u8()?;
+            let shndx = parser.parse_u16()?;
+            let value = parser.parse_class_sized()?;
+            let size = parser.parse_class_sized()?;
+            (value, size, info, other, shndx)
+        }
+    };
+
+    let symbol = Symbol {
+        name: String::new(), // Resolved by the caller, which knows the string table
+        value,
+        size,
+        info,
+        other,
+        shndx,
+        section_index: shndx as u32,
+    };
+    Ok((name_offset, symbol))
+}
+
+/// Parse the SHT_SYMTAB or SHT_DYNSYM section at `section_index`, resolving names
+/// through its linked string table and escaped section indexes through SHT_SYMTAB_SHNDX.
+/// A name that can't be read is `<corrupt>`, with a warning pushed to `warnings`.
+pub fn parse_symbol_table(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+    warnings: &mut Vec<ParseError>,
+) -> Result<SymbolTable, ParseError> {
+    let section = &sections[section_index];
+    let string_table = sections
+        .get(section.link as usize)
+        .ok_or(ParseError::BadOffset(section.link as u64))?;
+    let extended_indexes = sections
+        .iter()
+        .find(|sh| sh.section_type == abi::SHT_SYMTAB_SHNDX && sh.link as usize == section_index)
+        .map(|sh| section_data(buffer, sh))
+        .transpose()?;
+
+    let data = section_data(buffer, section)?;
+    let string_data = section_data(buffer, string_table)?;
+    let entry_size = usize::try_from(section.entry_size)?;
+
+    // Validation leaves a zero entry size only on empty tables
+    let mut symbols = Vec::new();
+    for entry_idx in 0..data.len().checked_div(entry_size).unwrap_or(0) {
+        let mut parser = Parser::new_with_offset(data, entry_size * entry_idx, class, endianness);
+        let (name_offset, mut symbol) = parse_symbol(&mut parser, class)?;
+        symbol.name = parse_name(string_data, name_offset as usize, warnings);
+        if symbol.shndx == abi::SHN_XINDEX {
+            if let Some(extended_indexes) = extended_indexes {
+                let mut parser =
+                    Parser::new_with_offset(extended_indexes, 4 * entry_idx, class, endianness);
+                symbol.section_index = parser.parse_u32()?;
+            }
+        }
+        symbols.push(symbol);
+    }
+
+    Ok(SymbolTable {
+        section_index,
+        symbols,
+    })
+}
+
+fn parse_relocation(
+    parser: &mut Parser,
+    class: Class,
+    has_addend: bool,
+) -> Result<Relocation, ParseError> {
+    let offset = parser.parse_class_sized()?;
+    let info = parser.parse_class_sized()?;
+    let (symbol_index, relocation_type) = match class {
+        Class::Elf32 => ((info >> 8) as u32, (info & 0xff) as u32),
+        Class::Elf64 => ((info >> 32) as u32, (info & 0xffffffff) as u32),
+    };
+    let addend = match (has_addend, class) {
+        (false, _) => None,
+        (true, Class::Elf32) => Some(parser.parse_u32()? as i32 as i64),
+        (true, Class::Elf64) => Some(parser.parse_u64()? as i64),
+    };
+
+    Ok(Relocation {
+        offset,
+        info,
+        symbol_index,
+        relocation_type,
+        addend,
+    })
+}
+
+/// Parse the SHT_REL or SHT_RELA section at `section_index`
+pub fn parse_relocation_table(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+) -> Result<RelocationTable, ParseError> {
+    let section = &sections[section_index];
+    let has_addend = section.section_type == abi::SHT_RELA;
+    let data = section_data(buffer, section)?;
+    let entry_size = usize::try_from(section.entry_size)?;
+
+    // Validation leaves a zero entry size only on empty tables
+    let mut relocations = Vec::new();
+    for entry_idx in 0..data.len().checked_div(entry_size).unwrap_or(0) {
+        let mut parser = Parser::new_with_offset(data, entry_size * entry_idx, class, endianness);
+        relocations.push(parse_relocation(&mut parser, class, has_addend)?);
+    }
+
+    Ok(RelocationTable {
+        section_index,
+        relocations,
+    })
+}
+
+fn parse_dynamic_entry(parser: &mut Parser, class: Class) -> Result<DynamicEntry, ParseError> {
+    let tag = match class {
+        Class::Elf32 => parser.parse_u32()? as i32 as i64,
+        Class::Elf64 => parser.parse_u64()? as i64,
+    };
+    let value = parser.parse_class_sized()?;
+    Ok(DynamicEntry {
+        tag,
+        value,
+        string: None, // Resolved once we've found DT_STRTAB
+    })
+}
+
+/// Parse the dynamic array from PT_DYNAMIC, or from the .dynamic section if there are no
+/// program headers. String values are resolved through DT_STRTAB. A string that can't be
+/// read is `<corrupt>`, and a dynamic array outside the file is left out, with a warning
+/// pushed to `warnings` for each.
+pub fn parse_dynamic_section(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    program_headers: &[ProgramHeader],
+    sections: &[SectionHeader],
+    warnings: &mut Vec<ParseError>,
+) -> Result<Option<DynamicSection>, ParseError> {
+    let dynamic_segment = program_headers
+        .iter()
+        .find(|ph| matches!(ph.header_type, HeaderType::Dynamic));
+    let dynamic_section = sections
+        .iter()
+        .find(|sh| sh.section_type == abi::SHT_DYNAMIC);
+    let (offset, size) = match (dynamic_segment, dynamic_section) {
+        (Some(ph), _) => (ph.offset, ph.size_in_file),
+        (None, Some(sh)) => (sh.offset, sh.size),
+        (None, None) => return Ok(None),
+    };
+
+    let data = match file_range(buffer, offset, size) {
+        Ok(data) => data,
+        Err(error) => {
+            warnings.push(error);
+            return Ok(None);
+        }
+    };
+    // PT_DYNAMIC doesn't declare an entry size, so go by the section's if there is one
+    let entry_size = match dynamic_section {
+        Some(sh) if sh.size > 0 => usize::try_from(sh.entry_size)?,
+        _ => class.dynamic_entry_size(),
+    };
+
+    let mut entries = Vec::new();
+    for entry_idx in 0..data.len().checked_div(entry_size).unwrap_or(0) {
+        let mut parser = Parser::new_with_offset(data, entry_size * entry_idx, class, endianness);
+        let entry = parse_dynamic_entry(&mut parser, class)?;
+        let is_end = entry.tag == abi::DT_NULL;
+        entries.push(entry);
+        if is_end {
+            break;
+        }
+    }
+
+    // DT_STRTAB is an address, so find the file bytes behind it. Objects without program
+    // headers can't be mapped that way, and a bad address can't be either, but the
+    // .dynamic section links to the same table.
+    let string_table = entries
+        .iter()
+        .find(|entry| entry.tag == abi::DT_STRTAB)
+        .and_then(|entry| virtual_address_to_offset(program_headers, entry.value));
+    let string_table_size = entries
+        .iter()
+        .find(|entry| entry.tag == abi::DT_STRSZ)
+        .map(|entry| entry.value);
+    let mapped_string_data = match (string_table, string_table_size) {
+        (Some(offset), Some(size)) => file_range(buffer, offset, size).ok(),
+        _ => None,
+    };
+    let string_data = match (mapped_string_data, dynamic_section) {
+        (Some(string_data), _) => Some(string_data),
+        (None, Some(sh)) => match sections.get(sh.link as usize) {
+            Some(string_table) => Some(section_data(buffer, string_table)?),
+            None => None,
+        },
+        (None, None) => None,
+    };
+
+    if let Some(string_data) = string_data {
+        for entry in entries.iter_mut().filter(|entry| entry.has_string_value()) {
+            // Too large for a usize is out of range too
+            let index = usize::try_from(entry.value).unwrap_or(usize::MAX);
+            entry.string = Some(parse_name(string_data, index, warnings));
+        }
+    }
+
+    Ok(Some(DynamicSection { offset, entries }))
+}
+
+/// Parse the SHT_GNU_VERDEF section at `section_index`. It holds `sh_info` Elf_Verdef
+/// entries, each pointing at its Elf_Verdaux names, chained together by byte offsets.
+/// A name that can't be read is `<corrupt>`, with a warning pushed to `warnings`.
+pub fn parse_version_definitions(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+    warnings: &mut Vec<ParseError>,
+) -> Result<VersionDefinitionTable, ParseError> {
+    let section = &sections[section_index];
+    let string_table = sections
+        .get(section.link as usize)
+        .ok_or(ParseError::BadOffset(section.link as u64))?;
+    let string_data = section_data(buffer, string_table)?;
+    let data = section_data(buffer, section)?;
+
+    let mut definitions = Vec::new();
+    let mut offset = 0;
+    for _ in 0..section.info {
+        let mut parser = Parser::new_with_offset(data, offset, class, endianness);
+        let version = parser.parse_u16()?;
+        let flags = parser.parse_u16()?;
+        let index = parser.parse_u16()?;
+        let count = parser.parse_u16()?;
+        parser.skip_u32(); // vd_hash
+        let aux = parser.parse_u32()? as usize;
+        let next = parser.parse_u32()? as usize;
+
+        let mut names = Vec::new();
+        let mut aux_offset = checked_end(offset, aux)?;
+        for _ in 0..count {
+            let mut parser = Parser::new_with_offset(data, aux_offset, class, endianness);
+            let name = parser.parse_u32()? as usize;
+            let aux_next = parser.parse_u32()? as usize;
+            names.push(VersionDefinitionName {
+                offset: aux_offset as u64,
+                name: parse_name(string_data, name, warnings),
+            });
+            // A link shorter than an Elf_Verdaux is corrupt, and could loop, so readelf stops
+            if aux_next < 8 {
+                break;
+            }
+            aux_offset = checked_end(aux_offset, aux_next)?;
+        }
+
+        definitions.push(VersionDefinition {
+            offset: offset as u64,
+            version,
+            flags,
+            index,
+            names,
+        });
+        if next < 20 {
+            break; // Zero ends the chain, and shorter than an Elf_Verdef is corrupt
+        }
+        offset = checked_end(offset, next)?;
+    }
+
+    Ok(VersionDefinitionTable {
+        section_index,
+        definitions,
+    })
+}
+
+/// Parse the SHT_GNU_VERNEED section at `section_index`. It holds `sh_info` Elf_Verneed
+/// entries, each pointing at its Elf_Vernaux requirements, chained together by byte offsets.
+/// A name that can't be read is `<corrupt>`, with a warning pushed to `warnings`.
+pub fn parse_version_needs(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+    warnings: &mut Vec<ParseError>,
+) -> Result<VersionNeedTable, ParseError> {
+    let section = &sections[section_index];
+    let string_table = sections
+        .get(section.link as usize)
+        .ok_or(ParseError::BadOffset(section.link as u64))?;
+    let string_data = section_data(buffer, string_table)?;
+    let data = section_data(buffer, section)?;
+
+    let mut needs = Vec::new();
+    let mut offset = 0;
+    for _ in 0..section.info {
+        let mut parser = Parser::new_with_offset(data, offset, class, endianness);
+        let version = parser.parse_u16()?;
+        let count = parser.parse_u16()?;
+        let file = parser.parse_u32()? as usize;
+        let aux = parser.parse_u32()? as usize;
+        let next = parser.parse_u32()? as usize;
+
+        let mut requirements = Vec::new();
+        let mut aux_offset = checked_end(offset, aux)?;
+        for _ in 0..count {
+            let mut parser = Parser::new_with_offset(data, aux_offset, class, endianness);
+            parser.skip_u32(); // vna_hash
+            let flags = parser.parse_u16()?;
+            let index = parser.parse_u16()?;
+            let name = parser.parse_u32()? as usize;
+            let aux_next = parser.parse_u32()? as usize;
+            requirements.push(VersionRequirement {
+                offset: aux_offset as u64,
+                flags,
+                index,
+                name: parse_name(string_data, name, warnings),
+            });
+            // A link shorter than an Elf_Vernaux is corrupt, and could loop, so readelf stops
+            if aux_next < 16 {
+                break;
+            }
+            aux_offset = checked_end(aux_offset, aux_next)?;
+        }
+
+        needs.push(VersionNeed {
+            offset: offset as u64,
+            version,
+            file: parse_name(string_data, file, warnings),
+            requirements,
+        });
+        if next < 16 {
+            break; // Zero ends the chain, and shorter than an Elf_Verneed is corrupt
+        }
+        offset = checked_end(offset, next)?;
+    }
+
+    Ok(VersionNeedTable {
+        section_index,
+        needs,
+    })
+}
+
+/// Parse the SHT_GNU_VERSYM section at `section_index`, one half-word per .dynsym symbol
+pub fn parse_version_symbols(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+) -> Result<VersionSymbolTable, ParseError> {
+    let data = section_data(buffer, &sections[section_index])?;
+    let mut parser = Parser::new(data, class, endianness);
+    let versions = (0..data.len() / 2)
+        .map(|_| parser.parse_u16())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(VersionSymbolTable {
+        section_index,
+        versions,
+    })
+}
+
+/// Parse the SHT_HASH section at `section_index`: nbucket, nchain, then both arrays.
+/// The entries are words, except on the 64-bit targets that declare 8-byte entries.
+pub fn parse_hash_table(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+) -> Result<HashTable, ParseError> {
+    let section = &sections[section_index];
+    let data = section_data(buffer, section)?;
+    let mut parser = Parser::new(data, class, endianness);
+    let parse_entry = |parser: &mut Parser| -> Result<u32, ParseError> {
+        match section.entry_size {
+            8 => Ok(u32::try_from(parser.parse_u64()?)?),
+            _ => parser.parse_u32(),
+        }
+    };
+
+    let bucket_count = parse_entry(&mut parser)?;
+    let chain_count = parse_entry(&mut parser)?;
+    let buckets = (0..bucket_count)
+        .map(|_| parse_entry(&mut parser))
+        .collect::<Result<Vec<_>, _>>()?;
+    let chains = (0..chain_count)
+        .map(|_| parse_entry(&mut parser))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HashTable {
+        section_index,
+        buckets,
+        chains,
+    })
+}
+
+/// Parse the SHT_GNU_HASH section at `section_index`: a four word header, the bloom
+/// filter in address-sized words, the buckets, then the chain up to the end of the section
+pub fn parse_gnu_hash_table(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    sections: &[SectionHeader],
+    section_index: usize,
+) -> Result<GnuHashTable, ParseError> {
+    let section = &sections[section_index];
+    let data = section_data(buffer, section)?;
+    let mut parser = Parser::new(data, class, endianness);
+
+    let bucket_count = parser.parse_u32()?;
+    let symbol_offset = parser.parse_u32()?;
+    let bloom_size = parser.parse_u32()?;
+    let bloom_shift = parser.parse_u32()?;
+    let bloom = (0..bloom_size)
+        .map(|_| parser.parse_class_sized())
+        .collect::<Result<Vec<_>, _>>()?;
+    let buckets = (0..bucket_count)
+        .map(|_| parser.parse_u32())
+        .collect::<Result<Vec<_>, _>>()?;
+    let chain_count = data.len().saturating_sub(parser.offset) / 4;
+    let chain = (0..chain_count)
+        .map(|_| parser.parse_u32())
+        .collect::<Result<Vec<_>, _>>()?;
+    let bloom_word_bits = match class {
+        Class::Elf32 => 32,
+        Class::Elf64 => 64,
+    };
+
+    Ok(GnuHashTable {
+        section_index,
+        symbol_offset,
+        bloom_shift,
+        bloom_word_bits,
+        bloom,
+        buckets,
+        chain,
+    })
+}
+
+/// Iterate over the entries of a note section or segment. Each entry is three words
+/// (namesz, descsz, type), then the name and the descriptor, each padded to `alignment`.
+pub struct NoteIterator<'buffer> {
+    parser: Parser<'buffer>,
+    alignment: usize,
+}
+
+impl<'buffer> NoteIterator<'buffer> {
+    /// Notes are 4-byte aligned, except the 8-byte aligned ones in some ELF64 files.
+    /// readelf treats any other alignment as 4 too.
+    pub fn new(data: &'buffer [u8], class: Class, endianness: Endianness, alignment: u64) -> Self {
+        let alignment = match alignment {
+            8 => 8,
+            _ => 4,
+        };
+        Self {
+            parser: Parser::new(data, class, endianness),
+            alignment,
+        }
+    }
+
+    fn align(&self, offset: usize) -> Result<usize, ParseError> {
+        offset
+            .checked_next_multiple_of(self.alignment)
+            .ok_or(ParseError::IntegerOverflow)
+    }
+
+    fn parse_note(&mut self) -> Result<Note, ParseError> {
+        let name_size = self.parser.parse_u32()? as usize;
+        let descriptor_size = self.parser.parse_u32()? as usize;
+        let note_type = self.parser.parse_u32()? as u64;
+
+        let name_start = self.parser.offset;
+        let name_end = checked_end(name_start, name_size)?;
+        let name_data = self
+            .parser
+            .buffer
+            .get(name_start..name_end)
+            .ok_or(ParseError::SliceReadError((name_start, name_end)))?;
+        // namesz counts the terminating NUL
+        let name_len = name_data
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(name_size);
+        // Not every owner name is UTF-8, and one that isn't shouldn't hide the rest
+        let name = String::from_utf8_lossy(&name_data[..name_len]).into_owned();
+
+        let descriptor_start = self.align(name_end)?;
+        let descriptor_end = checked_end(descriptor_start, descriptor_size)?;
+        let descriptor = self
+            .parser
+            .buffer
+            .get(descriptor_start..descriptor_end)
+            .ok_or(ParseError::SliceReadError((
+                descriptor_start,
+                descriptor_end,
+            )))?
+            .to_vec();
+        self.parser.offset = self.align(descriptor_end)?;
+
+        Ok(Note {
+            name,
+            note_type,
+            descriptor,
+        })
+    }
+}
+
+impl Iterator for NoteIterator<'_> {
+    type Item = Result<Note, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.offset >= self.parser.buffer.len() {
+            return None;
+        }
+        let note = self.parse_note();
+        if note.is_err() {
+            // The sizes can't be trusted past a bad entry, so stop there
+            self.parser.offset = self.parser.buffer.len();
+        }
+        Some(note)
+    }
+}
+
+/// Decode notes up to the first bad one, which goes in `warnings`, since the sizes
+/// can't be trusted past it
+fn parse_notes(
+    data: &[u8],
+    class: Class,
+    endianness: Endianness,
+    alignment: u64,
+    warnings: &mut Vec<ParseError>,
+) -> Vec<Note> {
+    let mut notes = Vec::new();
+    for note in NoteIterator::new(data, class, endianness, alignment) {
+        match note {
+            Ok(note) => notes.push(note),
+            Err(error) => warnings.push(error),
+        }
+    }
+    notes
+}
+
+/// Parse the notes in every SHT_NOTE section. Files without note sections, like core
+/// dumps, are read through their PT_NOTE segments instead, as readelf does. A note
+/// table that can't be read is left out, with the error in `warnings`.
+pub fn parse_note_tables(
+    buffer: &[u8],
+    class: Class,
+    endianness: Endianness,
+    program_headers: &[ProgramHeader],
+    sections: &[SectionHeader],
+    warnings: &mut Vec<ParseError>,
+) -> Vec<NoteTable> {
+    let mut note_tables = Vec::new();
+    let note_sections = sections
+        .iter()
+        .enumerate()
+        .filter(|(_, sh)| sh.section_type == abi::SHT_NOTE)
+        .collect::<Vec<_>>();
+    for &(idx, sh) in note_sections.iter() {
+        let data = match section_data(buffer, sh) {
+            Ok(data) => data,
+            Err(error) => {
+                warnings.push(error);
+                continue;
+            }
+        };
+        note_tables.push(NoteTable {
+            section_index: Some(idx),
+            offset: sh.offset,
+            size: sh.size,
+            notes: parse_notes(data, class, endianness, sh.alignment, warnings),
+        });
+    }
+    if !note_sections.is_empty() {
+        return note_tables;
+    }
+
+    for ph in program_headers.iter() {
+        if ph.header_type != HeaderType::Note {
+            continue;
+        }
+        let data = match file_range(buffer, ph.offset, ph.size_in_file) {
+            Ok(data) => data,
+            Err(error) => {
+                warnings.push(error);
+                continue;
+            }
+        };
+        note_tables.push(NoteTable {
+            section_index: None,
+            offset: ph.offset,
+            size: ph.size_in_file,
+            notes: parse_notes(data, class, endianness, ph.alignment, warnings),
+        });
+    }
+    note_tables
+}
+
+/// What to do when a structure's declared size doesn't match the size its class
+/// defines. A table entry smaller than that, zero included, would overlap the next
+/// one, so it's always an error.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Validation {
+    /// Fail with [`ParseError::BadEntsize`]
+    #[default]
+    Strict,
+    /// Record the mismatch in [`Elf::warnings`] and read with the declared size anyway
+    Lenient,
+}
+
+/// Check a declared size against the one the class defines
+fn check_size(
+    found: usize,
+    expected: usize,
+    validation: Validation,
+    warnings: &mut Vec<ParseError>,
+) -> Result<(), ParseError> {
+    if found == expected {
+        return Ok(());
+    }
+    let error = ParseError::BadEntsize((found as u64, expected as u64));
+    match validation {
+        Validation::Strict => Err(error),
+        Validation::Lenient => {
+            warnings.push(error);
+            Ok(())
+        }
+    }
+}
+
+/// Check a table's declared entry size, which can't be read past if it's smaller
+/// than an entry
+fn check_entry_size(
+    found: usize,
+    expected: usize,
+    validation: Validation,
+    warnings: &mut Vec<ParseError>,
+) -> Result<(), ParseError> {
+    if found < expected {
+        return Err(ParseError::BadEntsize((found as u64, expected as u64)));
+    }
+    check_size(found, expected, validation, warnings)
+}
+
+/// Check `e_ehsize`, `e_phentsize` and `e_shentsize`. An empty table has nothing to
+/// check, and its entry size is often left at zero.
+fn check_header_sizes(
+    header: &ElfHeader,
+    validation: Validation,
+    warnings: &mut Vec<ParseError>,
+) -> Result<(), ParseError> {
+    let class = header.class;
+    check_size(
+        header.elf_header_size,
+        class.elf_header_size(),
+        validation,
+        warnings,
+    )?;
+    if header.program_header_entries > 0 {
+        check_entry_size(
+            header.program_header_entry_size,
+            class.program_header_size(),
+            validation,
+            warnings,
+        )?;
+    }
+    if header.section_header_entries > 0 {
+        check_entry_size(
+            header.section_header_entry_size,
+            class.section_header_size(),
+            validation,
+            warnings,
+        )?;
+    }
+    Ok(())
+}
+
+/// Check the `sh_entsize` of the symbol, relocation and dynamic sections
+fn check_section_entry_sizes(
+    class: Class,
+    sections: &[SectionHeader],
+    validation: Validation,
+    warnings: &mut Vec<ParseError>,
+) -> Result<(), ParseError> {
+    for sh in sections.iter().filter(|sh| sh.size > 0) {
+        let expected = match sh.section_type {
+            abi::SHT_SYMTAB | abi::SHT_DYNSYM => class.symbol_size(),
+            abi::SHT_REL => class.relocation_size(),
+            abi::SHT_RELA => class.relocation_with_addend_size(),
+            abi::SHT_DYNAMIC => class.dynamic_entry_size(),
+            _ => continue,
+        };
+        let found = usize::try_from(sh.entry_size).unwrap_or(usize::MAX);
+        check_entry_size(found, expected, validation, warnings)?;
+    }
+    Ok(())
+}
+
+/// Parse a whole ELF file: its header, then every table the headers lead to. Entry sizes
+/// that don't match the class are an error.
 pub fn parse_elf(buffer: &[u8]) -> Result<Elf, ParseError> {
-    let elf_header = parse_elf_header(buffer)?;
+    parse_elf_with(buffer, Validation::Strict)
+}
+
+/// [`parse_elf`], choosing what to do about entry sizes that don't match the class
+pub fn parse_elf_with(buffer: &[u8], validation: Validation) -> Result<Elf, ParseError> {
+    let mut warnings = Vec::new();
+    let mut elf_header = parse_elf_header(buffer)?;
+    check_header_sizes(&elf_header, validation, &mut warnings)?;
 
     // Program Header Table
     let pht_offset = elf_header.program_header_offset;
     let pht_entry_size = elf_header.program_header_entry_size;
     let pht_entries = elf_header.program_header_entries;
-    let program_header_table =
-        parse_program_header_table(buffer, pht_offset, pht_entry_size, pht_entries)?;
+    let program_header_table = parse_program_header_table(
+        buffer,
+        elf_header.class,
+        elf_header.endianness,
+        pht_offset,
+        pht_entry_size,
+        pht_entries,
+    )?;
+
+    // Section Header Table
+    let sht_offset = elf_header.section_header_offset;
+    let sht_entry_size = elf_header.section_header_entry_size;
+    let sht_entries = elf_header.section_header_entries;
+    let mut section_header_table = parse_section_header_table(
+        buffer,
+        elf_header.class,
+        elf_header.endianness,
+        sht_offset,
+        sht_entry_size,
+        sht_entries,
+    )?;
+
+    check_section_entry_sizes(
+        elf_header.class,
+        &section_header_table,
+        validation,
+        &mut warnings,
+    )?;
+
+    // Section names live in .shstrtab, which is itself one of the sections
+    if let Some(string_table) = section_header_table.get(elf_header.string_table_index) {
+        // A bad name is shown as corrupt, the way readelf does, rather than failing the file
+        let names: Vec<String> = match section_data(buffer, string_table) {
+            Ok(table_data) => section_header_table
+                .iter()
+                .map(|sh| parse_name(table_data, sh.name_offset as usize, &mut warnings))
+                .collect(),
+            Err(error) => {
+                warnings.push(error);
+                vec![CORRUPT_NAME.to_string(); section_header_table.len()]
+            }
+        };
+        for (sh, name) in section_header_table.iter_mut().zip(names) {
+            sh.name = name;
+        }
+    }
+
+    let mut symbol_tables = Vec::new();
+    for (idx, sh) in section_header_table.iter().enumerate() {
+        if sh.section_type == abi::SHT_SYMTAB || sh.section_type == abi::SHT_DYNSYM {
+            let symbol_table = parse_symbol_table(
+                buffer,
+                elf_header.class,
+                elf_header.endianness,
+                &section_header_table,
+                idx,
+                &mut warnings,
+            )?;
+            symbol_tables.push(symbol_table);
+        }
+    }
+
+    let mut relocation_tables = Vec::new();
+    for (idx, sh) in section_header_table.iter().enumerate() {
+        if sh.section_type == abi::SHT_REL || sh.section_type == abi::SHT_RELA {
+            let relocation_table = parse_relocation_table(
+                buffer,
+                elf_header.class,
+                elf_header.endianness,
+                &section_header_table,
+                idx,
+            )?;
+            relocation_tables.push(relocation_table);
+        }
+    }
+
+    let dynamic = parse_dynamic_section(
+        buffer,
+        elf_header.class,
+        elf_header.endianness,
+        &program_header_table,
+        &section_header_table,
+        &mut warnings,
+    )?;
+    elf_header.is_pie = elf_header.file_type == FileType::Dyn
+        && dynamic
+            .iter()
+            .flat_map(|dynamic| dynamic.entries.iter())
+            .any(|entry| entry.tag == abi::DT_FLAGS_1 && entry.value & abi::DF_1_PIE as u64 != 0);
+
+    let mut version_definitions = None;
+    let mut version_needs = None;
+    let mut version_symbols = None;
+    let mut hash_table = None;
+    let mut gnu_hash_table = None;
+    for (idx, sh) in section_header_table.iter().enumerate() {
+        let (class, endianness) = (elf_header.class, elf_header.endianness);
+        match sh.section_type {
+            abi::SHT_GNU_VERDEF => {
+                let table = parse_version_definitions(
+                    buffer,
+                    class,
+                    endianness,
+                    &section_header_table,
+                    idx,
+                    &mut warnings,
+                )?;
+                version_definitions = Some(table);
+            }
+            abi::SHT_GNU_VERNEED => {
+                let table = parse_version_needs(
+                    buffer,
+                    class,
+                    endianness,
+                    &section_header_table,
+                    idx,
+                    &mut warnings,
+                )?;
+                version_needs = Some(table);
+            }
+            // A hash or version table that runs out of data is left out, as it would be
+            // in readelf, while the rest of the file is still read
+            abi::SHT_HASH => {
+                match parse_hash_table(buffer, class, endianness, &section_header_table, idx) {
+                    Ok(table) => hash_table = Some(table),
+                    Err(error) => warnings.push(error),
+                }
+            }
+            abi::SHT_GNU_HASH => {
+                let table =
+                    parse_gnu_hash_table(buffer, class, endianness, &section_header_table, idx);
+                let table = match table {
+                    Ok(table) => table,
+                    Err(error) => {
+                        warnings.push(error);
+                        continue;
+                    }
+                };
+                // Like readelf, leave a corrupt table out rather than guess at its chains
+                match (table.check_buckets(), validation) {
+                    (Ok(()), _) => gnu_hash_table = Some(table),
+                    (Err(error), Validation::Strict) => return Err(error),
+                    (Err(error), Validation::Lenient) => warnings.push(error),
+                }
+            }
+            abi::SHT_GNU_VERSYM => {
+                match parse_version_symbols(buffer, class, endianness, &section_header_table, idx) {
+                    Ok(table) => version_symbols = Some(table),
+                    Err(error) => warnings.push(error),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let note_tables = parse_note_tables(
+        buffer,
+        elf_header.class,
+        elf_header.endianness,
+        &program_header_table,
+        &section_header_table,
+        &mut warnings,
+    );
 
     Ok(Elf {
         header: elf_header,
         program_header_table,
+        section_header_table,
+        symbol_tables,
+        relocation_tables,
+        dynamic,
+        version_definitions,
+        version_needs,
+        version_symbols,
+        hash_table,
+        gnu_hash_table,
+        note_tables,
+        warnings,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::testing::{
+        build_elf_with, build_relocation_elf, build_versioned_elf, Section, Segment, Writer, RELAS,
+        RELS,
+    };
+
+    /// An ELF64 little-endian executable with `sections` and no segments
+    fn build_elf64(sections: &[Section]) -> Vec<u8> {
+        build_elf_with(Class::Elf64, Endianness::Little, 0, &[], sections)
+    }
+
+    fn writer64() -> Writer {
+        Writer::new(Class::Elf64, Endianness::Little)
+    }
+
+    /// Every class and byte order
+    const FORMATS: [(Class, Endianness); 4] = [
+        (Class::Elf32, Endianness::Little),
+        (Class::Elf32, Endianness::Big),
+        (Class::Elf64, Endianness::Little),
+        (Class::Elf64, Endianness::Big),
+    ];
+
+    #[test]
+    fn headers_in_every_format() {
+        for (class, endianness) in FORMATS {
+            let mut data = Section::new(".data", abi::SHT_PROGBITS, vec![1, 2, 3]);
+            data.flags = (abi::SHF_ALLOC | abi::SHF_WRITE) as u64;
+            data.address = 0x402000;
+            data.link = 7;
+            data.info = 9;
+            data.entry_size = 1;
+            let mut segment = Segment::new(0x401000, &[0xc3; 5]);
+            segment.physical_address = 0x1000;
+            segment.size_in_memory = 0x20;
+            let buffer = build_elf_with(class, endianness, 0x401004, &[segment], &[data]);
+            let elf = parse_elf(&buffer).unwrap();
+            let format = format!("{class:?} {endianness:?}");
+
+            let header = &elf.header;
+            assert_eq!((header.class, header.endianness), (class, endianness));
+            assert_eq!(header.file_type, FileType::Exec, "{format}");
+            assert_eq!(header.machine.0, abi::EM_X86_64, "{format}");
+            assert_eq!(header.version, 1, "{format}");
+            assert_eq!(header.entry, 0x401004, "{format}");
+            assert_eq!(header.elf_header_size, class.elf_header_size(), "{format}");
+            assert_eq!(header.program_header_offset, class.elf_header_size());
+            assert_eq!(
+                header.program_header_entry_size,
+                class.program_header_size(),
+                "{format}"
+            );
+            assert_eq!(
+                header.section_header_entry_size,
+                class.section_header_size(),
+                "{format}"
+            );
+            assert_eq!(header.program_header_entries, 1, "{format}");
+            assert_eq!(header.section_header_entries, 3, "{format}");
+            assert_eq!(header.string_table_index, 2, "{format}");
+
+            let ph = &elf.program_header_table[0];
+            let data_offset = (class.elf_header_size() + class.program_header_size()) as u64;
+            assert_eq!(ph.header_type, HeaderType::Load, "{format}");
+            assert_eq!(ph.offset, data_offset, "{format}");
+            assert_eq!(ph.virtual_address, 0x401000, "{format}");
+            assert_eq!(ph.physical_address, 0x1000, "{format}");
+            assert_eq!(ph.size_in_file, 5, "{format}");
+            assert_eq!(ph.size_in_memory, 0x20, "{format}");
+            assert_eq!(ph.flags, SegmentFlags(abi::PF_R | abi::PF_X), "{format}");
+            assert_eq!(ph.alignment, 1, "{format}");
+
+            let sh = &elf.section_header_table[1];
+            assert_eq!(sh.name, ".data", "{format}");
+            assert_eq!(sh.section_type, abi::SHT_PROGBITS, "{format}");
+            assert_eq!(
+                sh.flags,
+                (abi::SHF_ALLOC | abi::SHF_WRITE) as u64,
+                "{format}"
+            );
+            assert_eq!(sh.address, 0x402000, "{format}");
+            assert_eq!(sh.offset, data_offset + 5, "{format}");
+            assert_eq!(sh.size, 3, "{format}");
+            assert_eq!((sh.link, sh.info), (7, 9), "{format}");
+            assert_eq!((sh.alignment, sh.entry_size), (1, 1), "{format}");
+            assert_eq!(section_data(&buffer, sh).unwrap(), [1, 2, 3], "{format}");
+        }
+    }
+
+    #[test]
+    fn big_endian() {
+        let mut text = Section::new(".text", abi::SHT_PROGBITS, vec![0x4e, 0x75]);
+        text.flags = (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64;
+        text.address = 0x10000074;
+        let buffer = build_elf_with(
+            Class::Elf32,
+            Endianness::Big,
+            0x10000074,
+            &[Segment::new(0x10000000, &[0; 4])],
+            &[text],
+        );
+        let elf = parse_elf(&buffer).unwrap();
+
+        let header = &elf.header;
+        assert_eq!(header.endianness, Endianness::Big);
+        assert_eq!(header.file_type, FileType::Exec);
+        assert_eq!(header.machine.0, abi::EM_X86_64);
+        assert_eq!(header.entry, 0x10000074);
+        assert_eq!(header.elf_header_size, 52);
+        assert_eq!(header.program_header_entries, 1);
+        assert_eq!(header.section_header_entries, 3);
+        assert_eq!(header.string_table_index, 2);
+        assert_eq!(elf.program_header_table[0].virtual_address, 0x10000000);
+
+        let text = &elf.section_header_table[1];
+        assert_eq!(text.name, ".text");
+        assert_eq!(text.section_type, abi::SHT_PROGBITS);
+        assert_eq!(text.flags, (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64);
+        assert_eq!(text.address, 0x10000074);
+        assert_eq!(text.size, 2);
+        assert_eq!(section_data(&buffer, text).unwrap(), [0x4e, 0x75]);
+    }
+
+    #[test]
+    fn corrupt_dynamic_string() {
+        let strings = b"\0libc.so.6\0libm.so.6\0".to_vec();
+        let mut dynamic = writer64();
+        for (tag, value) in [
+            (abi::DT_NEEDED, 1),
+            (abi::DT_NEEDED, 0x1000),
+            (abi::DT_NEEDED, 11),
+        ] {
+            dynamic.u64(tag as u64);
+            dynamic.u64(value);
+        }
+        dynamic.u64(abi::DT_NULL as u64);
+        dynamic.u64(0);
+        let mut dynamic = Section::new(".dynamic", abi::SHT_DYNAMIC, dynamic.buffer);
+        dynamic.link = 1;
+        dynamic.entry_size = 16;
+        let buffer = build_elf64(&[Section::new(".dynstr", abi::SHT_STRTAB, strings), dynamic]);
+        let elf = parse_elf(&buffer).unwrap();
+
+        let strings: Vec<Option<&str>> = elf
+            .dynamic
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| entry.string.as_deref())
+            .collect();
+        assert_eq!(
+            strings,
+            [
+                Some("libc.so.6"),
+                Some(CORRUPT_NAME),
+                Some("libm.so.6"),
+                None
+            ]
+        );
+        assert!(matches!(elf.warnings[..], [ParseError::BadOffset(0x1000)]));
+    }
+
+    #[test]
+    fn dynamic_section_elf32_big_endian() {
+        let mut dynamic = Writer::new(Class::Elf32, Endianness::Big);
+        let entries = [
+            (abi::DT_NEEDED, 1),
+            (abi::DT_FLAGS, (abi::DF_BIND_NOW | abi::DF_ORIGIN) as u32),
+            (abi::DT_FLAGS_1, abi::DF_1_NOW as u32),
+            (abi::DT_NULL, 0),
+            (abi::DT_NEEDED, 1), // Past DT_NULL, so not read
+        ];
+        for (tag, value) in entries {
+            dynamic.u32(tag as u32);
+            dynamic.u32(value);
+        }
+        let mut dynamic = Section::new(".dynamic", abi::SHT_DYNAMIC, dynamic.buffer);
+        dynamic.link = 1;
+        dynamic.entry_size = 8;
+        let strings = b"\0libc.so.6\0".to_vec();
+        let buffer = build_elf_with(
+            Class::Elf32,
+            Endianness::Big,
+            0,
+            &[],
+            &[Section::new(".dynstr", abi::SHT_STRTAB, strings), dynamic],
+        );
+        let elf = parse_elf(&buffer).unwrap();
+
+        let dynamic = elf.dynamic.as_ref().unwrap();
+        assert_eq!(dynamic.offset, elf.section_header_table[2].offset);
+        let entries: Vec<(i64, u64, Option<&str>)> = dynamic
+            .entries
+            .iter()
+            .map(|entry| (entry.tag, entry.value, entry.string.as_deref()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (abi::DT_NEEDED, 1, Some("libc.so.6")),
+                (abi::DT_FLAGS, 0x9, None),
+                (abi::DT_FLAGS_1, 0x1, None),
+                (abi::DT_NULL, 0, None),
+            ]
+        );
+        assert!(elf.warnings.is_empty());
+    }
+
+    #[test]
+    fn note_name_not_utf8() {
+        let mut notes = writer64();
+        for (name, note_type) in [(&b"\xffGNU\0\0\0\0"[..], 1), (b"GNU\0", 3)] {
+            notes.u32(name.len() as u32);
+            notes.u32(4); // descsz
+            notes.u32(note_type);
+            notes.buffer.extend(name);
+            notes.u32(0x12345678);
+        }
+        let notes = NoteIterator::new(&notes.buffer, Class::Elf64, Endianness::Little, 4)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(notes[0].name, "\u{fffd}GNU");
+        assert_eq!(notes[1].name, "GNU");
+        assert_eq!(notes[1].note_type, 3);
+        assert_eq!(notes[1].descriptor, 0x12345678u32.to_le_bytes());
+    }
+
+    #[test]
+    fn truncated_note() {
+        let mut notes = writer64();
+        for descriptor_size in [4, 0x100] {
+            notes.u32(4); // namesz
+            notes.u32(descriptor_size);
+            notes.u32(abi::NT_GNU_BUILD_ID as u32);
+            notes.buffer.extend(b"GNU\0");
+            notes.u32(0x12345678);
+        }
+        let buffer = build_elf64(&[Section::new(".note", abi::SHT_NOTE, notes.buffer)]);
+        let elf = parse_elf(&buffer).unwrap();
+
+        let [table] = &elf.note_tables[..] else {
+            panic!("expected one note table");
+        };
+        let [note] = &table.notes[..] else {
+            panic!("expected only the note before the truncated one");
+        };
+        assert_eq!((note.name.as_str(), note.note_type), ("GNU", 3));
+        assert!(matches!(elf.warnings[..], [ParseError::SliceReadError(_)]));
+    }
+
+    #[test]
+    fn version_definitions() {
+        let elf = parse_elf(&build_versioned_elf()).unwrap();
+        let table = elf.version_definitions.as_ref().unwrap();
+        assert_eq!(table.section_index, 3);
+
+        let [base, definition] = &table.definitions[..] else {
+            panic!("expected two definitions");
+        };
+        assert_eq!(
+            (base.offset, base.flags, base.index),
+            (0, abi::VER_FLG_BASE, 1)
+        );
+        let names: Vec<(u64, &str)> = base
+            .names
+            .iter()
+            .map(|name| (name.offset, name.name.as_str()))
+            .collect();
+        assert_eq!(names, [(20, "libx.so")]); // vda_next 0 stops short of vd_cnt
+
+        assert_eq!((definition.offset, definition.index), (28, 2));
+        let names: Vec<(u64, &str)> = definition
+            .names
+            .iter()
+            .map(|name| (name.offset, name.name.as_str()))
+            .collect();
+        assert_eq!(names, [(48, "LIBX_2.0"), (56, CORRUPT_NAME)]);
+        assert!(elf
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseError::BadOffset(0x1000))));
+    }
+
+    #[test]
+    fn version_needs() {
+        let elf = parse_elf(&build_versioned_elf()).unwrap();
+        let table = elf.version_needs.as_ref().unwrap();
+        assert_eq!(table.section_index, 4);
+
+        let [libc, corrupt] = &table.needs[..] else {
+            panic!("expected two needs");
+        };
+        assert_eq!((libc.offset, libc.file.as_str()), (0, "libc.so.6"));
+        let requirements: Vec<(u64, u16, &str)> = libc
+            .requirements
+            .iter()
+            .map(|requirement| {
+                (
+                    requirement.offset,
+                    requirement.index,
+                    requirement.name.as_str(),
+                )
+            })
+            .collect();
+        // vna_next 0 stops short of vn_cnt
+        assert_eq!(
+            requirements,
+            [(16, 3, "GLIBC_2.34"), (32, 4, "GLIBC_2.2.5")]
+        );
+
+        assert_eq!((corrupt.offset, corrupt.file.as_str()), (48, CORRUPT_NAME));
+        assert_eq!(corrupt.requirements[0].name, "GLIBC_2.2.5");
+        assert!(elf
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseError::BadOffset(0x2000))));
+    }
+
+    #[test]
+    fn version_symbols() {
+        let elf = parse_elf(&build_versioned_elf()).unwrap();
+        let table = elf.version_symbols.as_ref().unwrap();
+        assert_eq!(table.section_index, 5);
+        assert_eq!(table.versions, [0, 2, 0x8002, 3, 2, 1, 0x8001]);
+    }
+
+    /// `.strtab`, then a `.symtab` of two symbols that declares `entry_size`. The entries
+    /// are laid out that far apart, but never closer than a symbol's size.
+    fn build_symbol_table(entry_size: u64) -> Vec<u8> {
+        let mut symbols = writer64();
+        for name in [0u32, 1] {
+            let start = symbols.buffer.len();
+            symbols.u32(name);
+            symbols.buffer.resize(start + 24, 0); // st_info to st_size
+            symbols
+                .buffer
+                .resize(start + entry_size.max(24) as usize, 0);
+        }
+        let mut symtab = Section::new(".symtab", abi::SHT_SYMTAB, symbols.buffer);
+        symtab.link = 1;
+        symtab.entry_size = entry_size;
+        build_elf64(&[
+            Section::new(".strtab", abi::SHT_STRTAB, b"\0main\0".to_vec()),
+            symtab,
+        ])
+    }
+
+    #[test]
+    fn oversized_entry_size() {
+        let buffer = build_symbol_table(32);
+        assert!(matches!(
+            parse_elf_with(&buffer, Validation::Strict),
+            Err(ParseError::BadEntsize((32, 24)))
+        ));
+
+        // Lenient reads with the declared size
+        let elf = parse_elf_with(&buffer, Validation::Lenient).unwrap();
+        assert!(matches!(
+            elf.warnings[..],
+            [ParseError::BadEntsize((32, 24))]
+        ));
+        let names: Vec<&str> = elf.symbol_tables[0]
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert_eq!(names, ["", "main"]);
+    }
+
+    #[test]
+    fn undersized_entry_size() {
+        let buffer = build_symbol_table(16);
+        for validation in [Validation::Strict, Validation::Lenient] {
+            assert!(matches!(
+                parse_elf_with(&buffer, validation),
+                Err(ParseError::BadEntsize((16, 24)))
+            ));
+        }
+    }
+
+    #[test]
+    fn matching_entry_size() {
+        let elf = parse_elf(&build_symbol_table(24)).unwrap();
+        assert!(elf.warnings.is_empty());
+        assert_eq!(elf.symbol_tables[0].symbols.len(), 2);
+    }
+
+    #[test]
+    fn extended_numbering() {
+        let mut text = Section::new(".text", abi::SHT_PROGBITS, vec![0xc3]);
+        text.flags = (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64;
+        let mut buffer = build_elf_with(
+            Class::Elf64,
+            Endianness::Little,
+            0x401000,
+            &[Segment::new(0x401000, &[0xc3])],
+            &[text],
+        );
+
+        // Escape e_phnum, e_shnum and e_shstrndx, and keep the real values in section 0's
+        // sh_info, sh_size and sh_link
+        let header = parse_elf_header(&buffer).unwrap();
+        buffer[56..58].copy_from_slice(&abi::PN_XNUM.to_le_bytes());
+        buffer[60..62].copy_from_slice(&0u16.to_le_bytes());
+        buffer[62..64].copy_from_slice(&abi::SHN_XINDEX.to_le_bytes());
+        let section_0 = header.section_header_offset;
+        buffer[section_0 + 32..section_0 + 40].copy_from_slice(&3u64.to_le_bytes());
+        buffer[section_0 + 40..section_0 + 44].copy_from_slice(&2u32.to_le_bytes());
+        buffer[section_0 + 44..section_0 + 48].copy_from_slice(&1u32.to_le_bytes());
+
+        let elf = parse_elf(&buffer).unwrap();
+        let header = &elf.header;
+        assert_eq!(header.stored_program_header_entries, abi::PN_XNUM);
+        assert_eq!(header.program_header_entries, 1);
+        assert_eq!(header.stored_section_header_entries, 0);
+        assert_eq!(header.section_header_entries, 3);
+        assert_eq!(header.stored_string_table_index, abi::SHN_XINDEX);
+        assert_eq!(header.string_table_index, 2);
+        assert_eq!(elf.program_header_table.len(), 1);
+        assert_eq!(elf.section_header_table[1].name, ".text");
+
+        let display = header.to_string();
+        assert!(display.contains("  Number of program headers:         65535 (1)\n"));
+        assert!(display.contains("  Number of section headers:         0 (3)\n"));
+        assert!(display.ends_with("  Section header string table index: 65535 (2)"));
+    }
+
+    #[test]
+    fn relocations_in_every_format() {
+        for (class, endianness) in FORMATS {
+            let elf = parse_elf(&build_relocation_elf(class, endianness)).unwrap();
+            let format = format!("{class:?} {endianness:?}");
+            let info_shift = match class {
+                Class::Elf32 => 8,
+                Class::Elf64 => 32,
+            };
+
+            let tables = &elf.relocation_tables;
+            assert_eq!(tables.len(), 2, "{format}");
+            for (table, (section_index, expected, has_addend)) in tables
+                .iter()
+                .zip([(3, &RELS[..], false), (4, &RELAS[..], true)])
+            {
+                assert_eq!(table.section_index, section_index, "{format}");
+                assert_eq!(table.relocations.len(), expected.len(), "{format}");
+                for (relocation, &(offset, symbol, relocation_type, addend)) in
+                    table.relocations.iter().zip(expected)
+                {
+                    assert_eq!(relocation.offset, offset, "{format}");
+                    assert_eq!(
+                        relocation.info,
+                        ((symbol as u64) << info_shift) | relocation_type as u64,
+                        "{format}"
+                    );
+                    assert_eq!(relocation.symbol_index, symbol, "{format}");
+                    assert_eq!(relocation.relocation_type, relocation_type, "{format}");
+                    assert_eq!(relocation.addend, has_addend.then_some(addend), "{format}");
+                }
+            }
+
+            // Both tables reach `counter` through their sh_link
+            let link = elf.section_header_table[3].link as usize;
+            let symbols = elf
+                .symbol_tables
+                .iter()
+                .find(|table| table.section_index == link)
+                .unwrap();
+            assert_eq!(
+                symbols.symbols[RELS[0].1 as usize].name, "counter",
+                "{format}"
+            );
+        }
+    }
+}
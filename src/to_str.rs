@@ -1,5 +1,5 @@
 //! Optional module for getting string representations of ELF constants
-//! Copied from: https://github.com/cole14/rust-elf/tree/master
+//! Copied from: <https://github.com/cole14/rust-elf/tree/master>
 use crate::abi;
 
 pub fn e_osabi_to_human_string(e_osabi: u8) -> Option<&'static str> {
@@ -274,6 +274,104 @@ pub fn e_machine_to_human_str(e_machine: u16) -> Option<&'static str> {
     }
 }
 
+/// Machine-specific `e_flags` bits the way readelf appends them after the hex value,
+/// e.g. ", Version5 EABI, hard-float ABI". Empty for machines we don't decode.
+pub fn e_flags_to_string(e_machine: u16, e_flags: u32) -> String {
+    let mut result = String::new();
+    if e_flags == 0 {
+        return result;
+    }
+    match e_machine {
+        abi::EM_ARM => {
+            let mut flags = e_flags & !abi::EF_ARM_EABIMASK;
+            for (flag, name) in [
+                (abi::EF_ARM_RELEXEC, "relocatable executable"),
+                (abi::EF_ARM_PIC, "position independent"),
+            ] {
+                if flags & flag != 0 {
+                    result.push_str(&format!(", {name}"));
+                    flags &= !flag;
+                }
+            }
+            let (version, known) = match e_flags & abi::EF_ARM_EABIMASK {
+                abi::EF_ARM_EABI_UNKNOWN => (
+                    "GNU EABI",
+                    [
+                        (abi::EF_ARM_SOFT_FLOAT, "software FP"),
+                        (abi::EF_ARM_VFP_FLOAT, "VFP"),
+                    ]
+                    .as_slice(),
+                ),
+                abi::EF_ARM_EABI_VER1 => ("Version1 EABI", [].as_slice()),
+                abi::EF_ARM_EABI_VER2 => ("Version2 EABI", [].as_slice()),
+                abi::EF_ARM_EABI_VER3 => ("Version3 EABI", [].as_slice()),
+                abi::EF_ARM_EABI_VER4 => (
+                    "Version4 EABI",
+                    [(abi::EF_ARM_BE8, "BE8"), (abi::EF_ARM_LE8, "LE8")].as_slice(),
+                ),
+                abi::EF_ARM_EABI_VER5 => (
+                    "Version5 EABI",
+                    [
+                        (abi::EF_ARM_BE8, "BE8"),
+                        (abi::EF_ARM_LE8, "LE8"),
+                        (abi::EF_ARM_ABI_FLOAT_SOFT, "soft-float ABI"),
+                        (abi::EF_ARM_ABI_FLOAT_HARD, "hard-float ABI"),
+                    ]
+                    .as_slice(),
+                ),
+                _ => ("<unrecognized EABI>", [].as_slice()),
+            };
+            result.push_str(&format!(", {version}"));
+            let mut remaining = flags;
+            for &(flag, name) in known {
+                if flags & flag != 0 {
+                    result.push_str(&format!(", {name}"));
+                    remaining &= !flag;
+                }
+            }
+            if remaining != 0 {
+                result.push_str(", <unknown>");
+            }
+        }
+        abi::EM_PPC => {
+            let names = [
+                (abi::EF_PPC_EMB, "emb"),
+                (abi::EF_PPC_RELOCATABLE, "relocatable"),
+                (abi::EF_PPC_RELOCATABLE_LIB, "relocatable-lib"),
+            ];
+            for (flag, name) in names {
+                if e_flags & flag != 0 {
+                    result.push_str(&format!(", {name}"));
+                }
+            }
+        }
+        abi::EM_PPC64 if e_flags & abi::EF_PPC64_ABI != 0 => {
+            result.push_str(&format!(", abiv{}", e_flags & abi::EF_PPC64_ABI));
+        }
+        abi::EM_RISCV => {
+            let names = [
+                (abi::EF_RISCV_RVC, "RVC"),
+                (abi::EF_RISCV_RVE, "RVE"),
+                (abi::EF_RISCV_TSO, "TSO"),
+            ];
+            for (flag, name) in names {
+                if e_flags & flag != 0 {
+                    result.push_str(&format!(", {name}"));
+                }
+            }
+            let float_abi = match e_flags & abi::EF_RISCV_FLOAT_ABI_MASK {
+                abi::EF_RISCV_FLOAT_ABI_SOFT => "soft-float",
+                abi::EF_RISCV_FLOAT_ABI_SINGLE => "single-float",
+                abi::EF_RISCV_FLOAT_ABI_DOUBLE => "double-float",
+                _ => "quad-float",
+            };
+            result.push_str(&format!(", {float_abi} ABI"));
+        }
+        _ => {}
+    }
+    result
+}
+
 pub fn e_machine_to_str(e_machine: u16) -> Option<&'static str> {
     match e_machine {
         abi::EM_NONE => Some("EM_NONE"),
@@ -506,16 +604,40 @@ pub fn sh_type_to_string(sh_type: u32) -> String {
     }
 }
 
-pub fn p_flags_to_string(p_flags: u32) -> String {
-    match p_flags < 8 {
-        true => {
-            let r = if p_flags & abi::PF_R != 0 { "R" } else { " " };
-            let w = if p_flags & abi::PF_W != 0 { "W" } else { " " };
-            let x = if p_flags & abi::PF_X != 0 { "E" } else { " " };
-            format!("{r}{w}{x}")
+pub fn sh_flags_to_string(sh_flags: u64) -> String {
+    // Same letters as the GNU readelf "Key to Flags"
+    let letters = [
+        (abi::SHF_WRITE, 'W'),
+        (abi::SHF_ALLOC, 'A'),
+        (abi::SHF_EXECINSTR, 'X'),
+        (abi::SHF_MERGE, 'M'),
+        (abi::SHF_STRINGS, 'S'),
+        (abi::SHF_INFO_LINK, 'I'),
+        (abi::SHF_LINK_ORDER, 'L'),
+        (abi::SHF_OS_NONCONFORMING, 'O'),
+        (abi::SHF_GROUP, 'G'),
+        (abi::SHF_TLS, 'T'),
+        (abi::SHF_COMPRESSED, 'C'),
+        (abi::SHF_EXCLUDE, 'E'),
+    ];
+    let mut result = String::new();
+    let mut known = (abi::SHF_MASKOS | abi::SHF_MASKPROC) as u64;
+    for (flag, letter) in letters {
+        known |= flag as u64;
+        if sh_flags & flag as u64 != 0 {
+            result.push(letter);
         }
-        false => format!("p_flags({p_flags:#x})"),
     }
+    if sh_flags & !known != 0 {
+        result.push('x');
+    }
+    if sh_flags & abi::SHF_MASKOS as u64 != 0 {
+        result.push('o');
+    }
+    if sh_flags & (abi::SHF_MASKPROC & !abi::SHF_EXCLUDE) as u64 != 0 {
+        result.push('p');
+    }
+    result
 }
 
 pub fn p_type_to_str(p_type: u32) -> Option<&'static str> {
@@ -616,6 +738,121 @@ pub fn note_abi_tag_os_to_str(os: u32) -> Option<&'static str> {
     }
 }
 
+/// Note type as GNU readelf describes it, for notes owned by "GNU"
+pub fn nt_gnu_to_str(n_type: u64) -> Option<&'static str> {
+    match n_type {
+        abi::NT_GNU_ABI_TAG => Some("NT_GNU_ABI_TAG (ABI version tag)"),
+        abi::NT_GNU_HWCAP => Some("NT_GNU_HWCAP (DSO-supplied software HWCAP info)"),
+        abi::NT_GNU_BUILD_ID => Some("NT_GNU_BUILD_ID (unique build ID bitstring)"),
+        abi::NT_GNU_GOLD_VERSION => Some("NT_GNU_GOLD_VERSION (gold version)"),
+        abi::NT_GNU_PROPERTY_TYPE_0 => Some("NT_GNU_PROPERTY_TYPE_0"),
+        _ => None,
+    }
+}
+
+/// Note type as GNU readelf describes it, for the notes in core files
+pub fn nt_core_to_str(n_type: u64) -> Option<&'static str> {
+    match n_type {
+        abi::NT_PRSTATUS => Some("NT_PRSTATUS (prstatus structure)"),
+        abi::NT_FPREGSET => Some("NT_FPREGSET (floating point registers)"),
+        abi::NT_PRPSINFO => Some("NT_PRPSINFO (prpsinfo structure)"),
+        abi::NT_TASKSTRUCT => Some("NT_TASKSTRUCT (task structure)"),
+        abi::NT_AUXV => Some("NT_AUXV (auxiliary vector)"),
+        abi::NT_PSTATUS => Some("NT_PSTATUS (pstatus structure)"),
+        abi::NT_PSINFO => Some("NT_PSINFO (psinfo structure)"),
+        abi::NT_LWPSTATUS => Some("NT_LWPSTATUS (lwpstatus_t structure)"),
+        abi::NT_LWPSINFO => Some("NT_LWPSINFO (lwpsinfo_t structure)"),
+        abi::NT_SIGINFO => Some("NT_SIGINFO (siginfo_t data)"),
+        abi::NT_FILE => Some("NT_FILE (mapped files)"),
+        abi::NT_PRXFPREG => Some("NT_PRXFPREG (user_xfpregs structure)"),
+        abi::NT_PPC_VMX => Some("NT_PPC_VMX (ppc Altivec registers)"),
+        abi::NT_PPC_VSX => Some("NT_PPC_VSX (ppc VSX registers)"),
+        abi::NT_386_TLS => Some("NT_386_TLS (x86 TLS information)"),
+        abi::NT_386_IOPERM => Some("NT_386_IOPERM (x86 I/O permissions)"),
+        abi::NT_X86_XSTATE => Some("NT_X86_XSTATE (x86 XSAVE extended state)"),
+        abi::NT_ARM_VFP => Some("NT_ARM_VFP (arm VFP registers)"),
+        abi::NT_ARM_TLS => Some("NT_ARM_TLS (AArch TLS registers)"),
+        abi::NT_ARM_HW_BREAK => Some("NT_ARM_HW_BREAK (AArch hardware breakpoint registers)"),
+        abi::NT_ARM_HW_WATCH => Some("NT_ARM_HW_WATCH (AArch hardware watchpoint registers)"),
+        abi::NT_ARM_SYSTEM_CALL => Some("NT_ARM_SYSTEM_CALL (AArch system call number)"),
+        abi::NT_ARM_SVE => Some("NT_ARM_SVE (AArch SVE registers)"),
+        abi::NT_ARM_PAC_MASK => Some("NT_ARM_PAC_MASK (AArch pointer authentication code masks)"),
+        abi::NT_ARM_TAGGED_ADDR_CTRL => {
+            Some("NT_ARM_TAGGED_ADDR_CTRL (AArch tagged address control)")
+        }
+        _ => None,
+    }
+}
+
+/// Comma-separated names of the bits set in a GNU property bitmask, as readelf shows them
+fn property_bits_to_string(value: u32, names: &[(u32, &str)]) -> String {
+    if value == 0 {
+        return "<None>".to_string();
+    }
+    let mut result = Vec::new();
+    let mut remaining = value;
+    for &(bit, name) in names {
+        if value & bit != 0 {
+            result.push(name.to_string());
+            remaining &= !bit;
+        }
+    }
+    if remaining != 0 {
+        result.push(format!("<unknown: {remaining:x}>"));
+    }
+    result.join(", ")
+}
+
+pub fn gnu_property_x86_feature_1_to_string(value: u32) -> String {
+    let names = [
+        (abi::GNU_PROPERTY_X86_FEATURE_1_IBT, "IBT"),
+        (abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK, "SHSTK"),
+        (abi::GNU_PROPERTY_X86_FEATURE_1_LAM_U48, "LAM_U48"),
+        (abi::GNU_PROPERTY_X86_FEATURE_1_LAM_U57, "LAM_U57"),
+    ];
+    property_bits_to_string(value, &names)
+}
+
+pub fn gnu_property_x86_feature_2_to_string(value: u32) -> String {
+    let names = [
+        (abi::GNU_PROPERTY_X86_FEATURE_2_X86, "x86"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_X87, "x87"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_MMX, "MMX"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_XMM, "XMM"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_YMM, "YMM"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_ZMM, "ZMM"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_FXSR, "FXSR"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_XSAVE, "XSAVE"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_XSAVEOPT, "XSAVEOPT"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_XSAVEC, "XSAVEC"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_TMM, "TMM"),
+        (abi::GNU_PROPERTY_X86_FEATURE_2_MASK, "MASK"),
+    ];
+    property_bits_to_string(value, &names)
+}
+
+pub fn gnu_property_x86_isa_1_to_string(value: u32) -> String {
+    // readelf leaves an empty ISA level blank rather than writing <None>
+    if value == 0 {
+        return String::new();
+    }
+    let names = [
+        (abi::GNU_PROPERTY_X86_ISA_1_BASELINE, "x86-64-baseline"),
+        (abi::GNU_PROPERTY_X86_ISA_1_V2, "x86-64-v2"),
+        (abi::GNU_PROPERTY_X86_ISA_1_V3, "x86-64-v3"),
+        (abi::GNU_PROPERTY_X86_ISA_1_V4, "x86-64-v4"),
+    ];
+    property_bits_to_string(value, &names)
+}
+
+pub fn gnu_property_aarch64_feature_1_to_string(value: u32) -> String {
+    let names = [
+        (abi::GNU_PROPERTY_AARCH64_FEATURE_1_BTI, "BTI"),
+        (abi::GNU_PROPERTY_AARCH64_FEATURE_1_PAC, "PAC"),
+    ];
+    property_bits_to_string(value, &names)
+}
+
 pub fn d_tag_to_str(d_tag: i64) -> Option<&'static str> {
     match d_tag {
         abi::DT_NULL => Some("DT_NULL"),
@@ -694,3 +931,925 @@ pub fn d_tag_to_str(d_tag: i64) -> Option<&'static str> {
         _ => None,
     }
 }
+
+/// Space-separated names of the bits set in `value`, with any unnamed bits left over in hex
+fn flags_to_string(value: u64, names: &[(i64, &str)]) -> String {
+    let mut result = Vec::new();
+    let mut remaining = value;
+    for &(flag, name) in names {
+        if value & flag as u64 != 0 {
+            result.push(name.to_string());
+            remaining &= !(flag as u64);
+        }
+    }
+    if remaining != 0 {
+        result.push(format!("{remaining:#x}"));
+    }
+    result.join(" ")
+}
+
+pub fn d_flags_to_string(d_flags: u64) -> String {
+    let names = [
+        (abi::DF_ORIGIN, "ORIGIN"),
+        (abi::DF_SYMBOLIC, "SYMBOLIC"),
+        (abi::DF_TEXTREL, "TEXTREL"),
+        (abi::DF_BIND_NOW, "BIND_NOW"),
+        (abi::DF_STATIC_TLS, "STATIC_TLS"),
+    ];
+    flags_to_string(d_flags, &names)
+}
+
+/// `vd_flags` or `vna_flags` the way readelf shows them, e.g. BASE | WEAK
+pub fn ver_flags_to_string(flags: u16) -> String {
+    if flags == 0 {
+        return "none".to_string();
+    }
+    let names = [
+        (abi::VER_FLG_BASE, "BASE"),
+        (abi::VER_FLG_WEAK, "WEAK"),
+        (abi::VER_FLG_INFO, "INFO"),
+    ];
+    let mut result = Vec::new();
+    let mut remaining = flags;
+    for (flag, name) in names {
+        if flags & flag != 0 {
+            result.push(name);
+            remaining &= !flag;
+        }
+    }
+    if remaining != 0 {
+        result.push("<unknown>");
+    }
+    result.join(" | ")
+}
+
+pub fn d_flags_1_to_string(d_flags_1: u64) -> String {
+    let names = [
+        (abi::DF_1_NOW, "NOW"),
+        (abi::DF_1_GLOBAL, "GLOBAL"),
+        (abi::DF_1_GROUP, "GROUP"),
+        (abi::DF_1_NODELETE, "NODELETE"),
+        (abi::DF_1_LOADFLTR, "LOADFLTR"),
+        (abi::DF_1_INITFIRST, "INITFIRST"),
+        (abi::DF_1_NOOPEN, "NOOPEN"),
+        (abi::DF_1_ORIGIN, "ORIGIN"),
+        (abi::DF_1_DIRECT, "DIRECT"),
+        (abi::DF_1_TRANS, "TRANS"),
+        (abi::DF_1_INTERPOSE, "INTERPOSE"),
+        (abi::DF_1_NODEFLIB, "NODEFLIB"),
+        (abi::DF_1_NODUMP, "NODUMP"),
+        (abi::DF_1_CONFALT, "CONFALT"),
+        (abi::DF_1_ENDFILTEE, "ENDFILTEE"),
+        (abi::DF_1_DISPRELDNE, "DISPRELDNE"),
+        (abi::DF_1_DISPRELPND, "DISPRELPND"),
+        (abi::DF_1_NODIRECT, "NODIRECT"),
+        (abi::DF_1_IGNMULDEF, "IGNMULDEF"),
+        (abi::DF_1_NOKSYMS, "NOKSYMS"),
+        (abi::DF_1_NOHDR, "NOHDR"),
+        (abi::DF_1_EDITED, "EDITED"),
+        (abi::DF_1_NORELOC, "NORELOC"),
+        (abi::DF_1_SYMINTPOSE, "SYMINTPOSE"),
+        (abi::DF_1_GLOBAUDIT, "GLOBAUDIT"),
+        (abi::DF_1_SINGLETON, "SINGLETON"),
+        (abi::DF_1_STUB, "STUB"),
+        (abi::DF_1_PIE, "PIE"),
+        (abi::DF_1_KMOD, "KMOD"),
+        (abi::DF_1_WEAKFILTER, "WEAKFILTER"),
+        (abi::DF_1_NOCOMMON, "NOCOMMON"),
+    ];
+    flags_to_string(d_flags_1, &names)
+}
+
+pub fn r_aarch64_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_AARCH64_NONE => Some("R_AARCH64_NONE"),
+        abi::R_AARCH64_P32_ABS32 => Some("R_AARCH64_P32_ABS32"),
+        abi::R_AARCH64_P32_COPY => Some("R_AARCH64_P32_COPY"),
+        abi::R_AARCH64_P32_GLOB_DAT => Some("R_AARCH64_P32_GLOB_DAT"),
+        abi::R_AARCH64_P32_JUMP_SLOT => Some("R_AARCH64_P32_JUMP_SLOT"),
+        abi::R_AARCH64_P32_RELATIVE => Some("R_AARCH64_P32_RELATIVE"),
+        abi::R_AARCH64_P32_TLS_DTPMOD => Some("R_AARCH64_P32_TLS_DTPMOD"),
+        abi::R_AARCH64_P32_TLS_DTPREL => Some("R_AARCH64_P32_TLS_DTPREL"),
+        abi::R_AARCH64_P32_TLS_TPREL => Some("R_AARCH64_P32_TLS_TPREL"),
+        abi::R_AARCH64_P32_TLSDESC => Some("R_AARCH64_P32_TLSDESC"),
+        abi::R_AARCH64_P32_IRELATIVE => Some("R_AARCH64_P32_IRELATIVE"),
+        abi::R_AARCH64_ABS64 => Some("R_AARCH64_ABS64"),
+        abi::R_AARCH64_ABS32 => Some("R_AARCH64_ABS32"),
+        abi::R_AARCH64_ABS16 => Some("R_AARCH64_ABS16"),
+        abi::R_AARCH64_PREL64 => Some("R_AARCH64_PREL64"),
+        abi::R_AARCH64_PREL32 => Some("R_AARCH64_PREL32"),
+        abi::R_AARCH64_PREL16 => Some("R_AARCH64_PREL16"),
+        abi::R_AARCH64_MOVW_UABS_G0 => Some("R_AARCH64_MOVW_UABS_G0"),
+        abi::R_AARCH64_MOVW_UABS_G0_NC => Some("R_AARCH64_MOVW_UABS_G0_NC"),
+        abi::R_AARCH64_MOVW_UABS_G1 => Some("R_AARCH64_MOVW_UABS_G1"),
+        abi::R_AARCH64_MOVW_UABS_G1_NC => Some("R_AARCH64_MOVW_UABS_G1_NC"),
+        abi::R_AARCH64_MOVW_UABS_G2 => Some("R_AARCH64_MOVW_UABS_G2"),
+        abi::R_AARCH64_MOVW_UABS_G2_NC => Some("R_AARCH64_MOVW_UABS_G2_NC"),
+        abi::R_AARCH64_MOVW_UABS_G3 => Some("R_AARCH64_MOVW_UABS_G3"),
+        abi::R_AARCH64_MOVW_SABS_G0 => Some("R_AARCH64_MOVW_SABS_G0"),
+        abi::R_AARCH64_MOVW_SABS_G1 => Some("R_AARCH64_MOVW_SABS_G1"),
+        abi::R_AARCH64_MOVW_SABS_G2 => Some("R_AARCH64_MOVW_SABS_G2"),
+        abi::R_AARCH64_LD_PREL_LO19 => Some("R_AARCH64_LD_PREL_LO19"),
+        abi::R_AARCH64_ADR_PREL_LO21 => Some("R_AARCH64_ADR_PREL_LO21"),
+        abi::R_AARCH64_ADR_PREL_PG_HI21 => Some("R_AARCH64_ADR_PREL_PG_HI21"),
+        abi::R_AARCH64_ADR_PREL_PG_HI21_NC => Some("R_AARCH64_ADR_PREL_PG_HI21_NC"),
+        abi::R_AARCH64_ADD_ABS_LO12_NC => Some("R_AARCH64_ADD_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST8_ABS_LO12_NC => Some("R_AARCH64_LDST8_ABS_LO12_NC"),
+        abi::R_AARCH64_TSTBR14 => Some("R_AARCH64_TSTBR14"),
+        abi::R_AARCH64_CONDBR19 => Some("R_AARCH64_CONDBR19"),
+        abi::R_AARCH64_JUMP26 => Some("R_AARCH64_JUMP26"),
+        abi::R_AARCH64_CALL26 => Some("R_AARCH64_CALL26"),
+        abi::R_AARCH64_LDST16_ABS_LO12_NC => Some("R_AARCH64_LDST16_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST32_ABS_LO12_NC => Some("R_AARCH64_LDST32_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST64_ABS_LO12_NC => Some("R_AARCH64_LDST64_ABS_LO12_NC"),
+        abi::R_AARCH64_MOVW_PREL_G0 => Some("R_AARCH64_MOVW_PREL_G0"),
+        abi::R_AARCH64_MOVW_PREL_G0_NC => Some("R_AARCH64_MOVW_PREL_G0_NC"),
+        abi::R_AARCH64_MOVW_PREL_G1 => Some("R_AARCH64_MOVW_PREL_G1"),
+        abi::R_AARCH64_MOVW_PREL_G1_NC => Some("R_AARCH64_MOVW_PREL_G1_NC"),
+        abi::R_AARCH64_MOVW_PREL_G2 => Some("R_AARCH64_MOVW_PREL_G2"),
+        abi::R_AARCH64_MOVW_PREL_G2_NC => Some("R_AARCH64_MOVW_PREL_G2_NC"),
+        abi::R_AARCH64_MOVW_PREL_G3 => Some("R_AARCH64_MOVW_PREL_G3"),
+        abi::R_AARCH64_LDST128_ABS_LO12_NC => Some("R_AARCH64_LDST128_ABS_LO12_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G0 => Some("R_AARCH64_MOVW_GOTOFF_G0"),
+        abi::R_AARCH64_MOVW_GOTOFF_G0_NC => Some("R_AARCH64_MOVW_GOTOFF_G0_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G1 => Some("R_AARCH64_MOVW_GOTOFF_G1"),
+        abi::R_AARCH64_MOVW_GOTOFF_G1_NC => Some("R_AARCH64_MOVW_GOTOFF_G1_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G2 => Some("R_AARCH64_MOVW_GOTOFF_G2"),
+        abi::R_AARCH64_MOVW_GOTOFF_G2_NC => Some("R_AARCH64_MOVW_GOTOFF_G2_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G3 => Some("R_AARCH64_MOVW_GOTOFF_G3"),
+        abi::R_AARCH64_GOTREL64 => Some("R_AARCH64_GOTREL64"),
+        abi::R_AARCH64_GOTREL32 => Some("R_AARCH64_GOTREL32"),
+        abi::R_AARCH64_GOT_LD_PREL19 => Some("R_AARCH64_GOT_LD_PREL19"),
+        abi::R_AARCH64_LD64_GOTOFF_LO15 => Some("R_AARCH64_LD64_GOTOFF_LO15"),
+        abi::R_AARCH64_ADR_GOT_PAGE => Some("R_AARCH64_ADR_GOT_PAGE"),
+        abi::R_AARCH64_LD64_GOT_LO12_NC => Some("R_AARCH64_LD64_GOT_LO12_NC"),
+        abi::R_AARCH64_LD64_GOTPAGE_LO15 => Some("R_AARCH64_LD64_GOTPAGE_LO15"),
+        abi::R_AARCH64_TLSGD_ADR_PREL21 => Some("R_AARCH64_TLSGD_ADR_PREL21"),
+        abi::R_AARCH64_TLSGD_ADR_PAGE21 => Some("R_AARCH64_TLSGD_ADR_PAGE21"),
+        abi::R_AARCH64_TLSGD_ADD_LO12_NC => Some("R_AARCH64_TLSGD_ADD_LO12_NC"),
+        abi::R_AARCH64_TLSGD_MOVW_G1 => Some("R_AARCH64_TLSGD_MOVW_G1"),
+        abi::R_AARCH64_TLSGD_MOVW_G0_NC => Some("R_AARCH64_TLSGD_MOVW_G0_NC"),
+        abi::R_AARCH64_TLSLD_ADR_PREL21 => Some("R_AARCH64_TLSLD_ADR_PREL21"),
+        abi::R_AARCH64_TLSLD_ADR_PAGE21 => Some("R_AARCH64_TLSLD_ADR_PAGE21"),
+        abi::R_AARCH64_TLSLD_ADD_LO12_NC => Some("R_AARCH64_TLSLD_ADD_LO12_NC"),
+        abi::R_AARCH64_TLSLD_MOVW_G1 => Some("R_AARCH64_TLSLD_MOVW_G1"),
+        abi::R_AARCH64_TLSLD_MOVW_G0_NC => Some("R_AARCH64_TLSLD_MOVW_G0_NC"),
+        abi::R_AARCH64_TLSLD_LD_PREL19 => Some("R_AARCH64_TLSLD_LD_PREL19"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G2 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G2"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G1"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G0"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_HI12 => Some("R_AARCH64_TLSLD_ADD_DTPREL_HI12"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12 => Some("R_AARCH64_TLSLD_ADD_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST8_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST16_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST32_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST64_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G1 => Some("R_AARCH64_TLSIE_MOVW_GOTTPREL_G1"),
+        abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC => Some("R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC"),
+        abi::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21 => Some("R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21"),
+        abi::R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC => Some("R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSIE_LD_GOTTPREL_PREL19 => Some("R_AARCH64_TLSIE_LD_GOTTPREL_PREL19"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G2 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G2"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G1 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G1"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G1_NC => Some("R_AARCH64_TLSLE_MOVW_TPREL_G1_NC"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G0 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G0"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G0_NC => Some("R_AARCH64_TLSLE_MOVW_TPREL_G0_NC"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_HI12 => Some("R_AARCH64_TLSLE_ADD_TPREL_HI12"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_LO12 => Some("R_AARCH64_TLSLE_ADD_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_ADD_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST8_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST16_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST32_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST64_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSDESC_LD_PREL19 => Some("R_AARCH64_TLSDESC_LD_PREL19"),
+        abi::R_AARCH64_TLSDESC_ADR_PREL21 => Some("R_AARCH64_TLSDESC_ADR_PREL21"),
+        abi::R_AARCH64_TLSDESC_ADR_PAGE21 => Some("R_AARCH64_TLSDESC_ADR_PAGE21"),
+        abi::R_AARCH64_TLSDESC_LD64_LO12 => Some("R_AARCH64_TLSDESC_LD64_LO12"),
+        abi::R_AARCH64_TLSDESC_ADD_LO12 => Some("R_AARCH64_TLSDESC_ADD_LO12"),
+        abi::R_AARCH64_TLSDESC_OFF_G1 => Some("R_AARCH64_TLSDESC_OFF_G1"),
+        abi::R_AARCH64_TLSDESC_OFF_G0_NC => Some("R_AARCH64_TLSDESC_OFF_G0_NC"),
+        abi::R_AARCH64_TLSDESC_LDR => Some("R_AARCH64_TLSDESC_LDR"),
+        abi::R_AARCH64_TLSDESC_ADD => Some("R_AARCH64_TLSDESC_ADD"),
+        abi::R_AARCH64_TLSDESC_CALL => Some("R_AARCH64_TLSDESC_CALL"),
+        abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST128_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST128_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC => {
+            Some("R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC")
+        }
+        abi::R_AARCH64_COPY => Some("R_AARCH64_COPY"),
+        abi::R_AARCH64_GLOB_DAT => Some("R_AARCH64_GLOB_DAT"),
+        abi::R_AARCH64_JUMP_SLOT => Some("R_AARCH64_JUMP_SLOT"),
+        abi::R_AARCH64_RELATIVE => Some("R_AARCH64_RELATIVE"),
+        abi::R_AARCH64_TLS_DTPMOD => Some("R_AARCH64_TLS_DTPMOD"),
+        abi::R_AARCH64_TLS_DTPREL => Some("R_AARCH64_TLS_DTPREL"),
+        abi::R_AARCH64_TLS_TPREL => Some("R_AARCH64_TLS_TPREL"),
+        abi::R_AARCH64_TLSDESC => Some("R_AARCH64_TLSDESC"),
+        abi::R_AARCH64_IRELATIVE => Some("R_AARCH64_IRELATIVE"),
+        _ => None,
+    }
+}
+
+pub fn r_arm_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_ARM_NONE => Some("R_ARM_NONE"),
+        abi::R_ARM_PC24 => Some("R_ARM_PC24"),
+        abi::R_ARM_ABS32 => Some("R_ARM_ABS32"),
+        abi::R_ARM_REL32 => Some("R_ARM_REL32"),
+        abi::R_ARM_LDR_PC_G0 => Some("R_ARM_LDR_PC_G0"),
+        abi::R_ARM_ABS16 => Some("R_ARM_ABS16"),
+        abi::R_ARM_ABS12 => Some("R_ARM_ABS12"),
+        abi::R_ARM_THM_ABS5 => Some("R_ARM_THM_ABS5"),
+        abi::R_ARM_ABS8 => Some("R_ARM_ABS8"),
+        abi::R_ARM_SBREL32 => Some("R_ARM_SBREL32"),
+        abi::R_ARM_THM_CALL => Some("R_ARM_THM_CALL"),
+        abi::R_ARM_THM_PC8 => Some("R_ARM_THM_PC8"),
+        abi::R_ARM_BREL_ADJ => Some("R_ARM_BREL_ADJ"),
+        abi::R_ARM_TLS_DESC => Some("R_ARM_TLS_DESC"),
+        abi::R_ARM_THM_SWI8 => Some("R_ARM_THM_SWI8"),
+        abi::R_ARM_XPC25 => Some("R_ARM_XPC25"),
+        abi::R_ARM_THM_XPC22 => Some("R_ARM_THM_XPC22"),
+        abi::R_ARM_TLS_DTPMOD32 => Some("R_ARM_TLS_DTPMOD32"),
+        abi::R_ARM_TLS_DTPOFF32 => Some("R_ARM_TLS_DTPOFF32"),
+        abi::R_ARM_TLS_TPOFF32 => Some("R_ARM_TLS_TPOFF32"),
+        abi::R_ARM_COPY => Some("R_ARM_COPY"),
+        abi::R_ARM_GLOB_DAT => Some("R_ARM_GLOB_DAT"),
+        abi::R_ARM_JUMP_SLOT => Some("R_ARM_JUMP_SLOT"),
+        abi::R_ARM_RELATIVE => Some("R_ARM_RELATIVE"),
+        abi::R_ARM_GOTOFF32 => Some("R_ARM_GOTOFF32"),
+        abi::R_ARM_BASE_PREL => Some("R_ARM_BASE_PREL"),
+        abi::R_ARM_BASE_BREL => Some("R_ARM_BASE_BREL"),
+        abi::R_ARM_PLT32 => Some("R_ARM_PLT32"),
+        abi::R_ARM_CALL => Some("R_ARM_CALL"),
+        abi::R_ARM_JUMP24 => Some("R_ARM_JUMP24"),
+        abi::R_ARM_THM_JUMP24 => Some("R_ARM_THM_JUMP24"),
+        abi::R_ARM_BASE_ABS => Some("R_ARM_BASE_ABS"),
+        abi::R_ARM_ALU_PCREL_7_0 => Some("R_ARM_ALU_PCREL_7_0"),
+        abi::R_ARM_ALU_PCREL_15_8 => Some("R_ARM_ALU_PCREL_15_8"),
+        abi::R_ARM_ALU_PCREL_23_15 => Some("R_ARM_ALU_PCREL_23_15"),
+        abi::R_ARM_LDR_SBREL_11_0 => Some("R_ARM_LDR_SBREL_11_0"),
+        abi::R_ARM_ALU_SBREL_19_12 => Some("R_ARM_ALU_SBREL_19_12"),
+        abi::R_ARM_ALU_SBREL_27_20 => Some("R_ARM_ALU_SBREL_27_20"),
+        abi::R_ARM_TARGET1 => Some("R_ARM_TARGET1"),
+        abi::R_ARM_SBREL31 => Some("R_ARM_SBREL31"),
+        abi::R_ARM_V4BX => Some("R_ARM_V4BX"),
+        abi::R_ARM_TARGET2 => Some("R_ARM_TARGET2"),
+        abi::R_ARM_PREL31 => Some("R_ARM_PREL31"),
+        abi::R_ARM_MOVW_ABS_NC => Some("R_ARM_MOVW_ABS_NC"),
+        abi::R_ARM_MOVT_ABS => Some("R_ARM_MOVT_ABS"),
+        abi::R_ARM_MOVW_PREL_NC => Some("R_ARM_MOVW_PREL_NC"),
+        abi::R_ARM_MOVT_PREL => Some("R_ARM_MOVT_PREL"),
+        abi::R_ARM_THM_MOVW_ABS_NC => Some("R_ARM_THM_MOVW_ABS_NC"),
+        abi::R_ARM_THM_MOVT_ABS => Some("R_ARM_THM_MOVT_ABS"),
+        abi::R_ARM_THM_MOVW_PREL_NC => Some("R_ARM_THM_MOVW_PREL_NC"),
+        abi::R_ARM_THM_MOVT_PREL => Some("R_ARM_THM_MOVT_PREL"),
+        abi::R_ARM_THM_JUMP19 => Some("R_ARM_THM_JUMP19"),
+        abi::R_ARM_THM_JUMP6 => Some("R_ARM_THM_JUMP6"),
+        abi::R_ARM_THM_ALU_PREL_11_0 => Some("R_ARM_THM_ALU_PREL_11_0"),
+        abi::R_ARM_THM_PC12 => Some("R_ARM_THM_PC12"),
+        abi::R_ARM_ABS32_NOI => Some("R_ARM_ABS32_NOI"),
+        abi::R_ARM_REL32_NOI => Some("R_ARM_REL32_NOI"),
+        abi::R_ARM_ALU_PC_G0_NC => Some("R_ARM_ALU_PC_G0_NC"),
+        abi::R_ARM_ALU_PC_G0 => Some("R_ARM_ALU_PC_G0"),
+        abi::R_ARM_ALU_PC_G1_NC => Some("R_ARM_ALU_PC_G1_NC"),
+        abi::R_ARM_ALU_PC_G1 => Some("R_ARM_ALU_PC_G1"),
+        abi::R_ARM_ALU_PC_G2 => Some("R_ARM_ALU_PC_G2"),
+        abi::R_ARM_LDR_PC_G1 => Some("R_ARM_LDR_PC_G1"),
+        abi::R_ARM_LDR_PC_G2 => Some("R_ARM_LDR_PC_G2"),
+        abi::R_ARM_LDRS_PC_G0 => Some("R_ARM_LDRS_PC_G0"),
+        abi::R_ARM_LDRS_PC_G1 => Some("R_ARM_LDRS_PC_G1"),
+        abi::R_ARM_LDRS_PC_G2 => Some("R_ARM_LDRS_PC_G2"),
+        abi::R_ARM_LDC_PC_G0 => Some("R_ARM_LDC_PC_G0"),
+        abi::R_ARM_LDC_PC_G1 => Some("R_ARM_LDC_PC_G1"),
+        abi::R_ARM_LDC_PC_G2 => Some("R_ARM_LDC_PC_G2"),
+        abi::R_ARM_ALU_SB_G0_NC => Some("R_ARM_ALU_SB_G0_NC"),
+        abi::R_ARM_ALU_SB_G0 => Some("R_ARM_ALU_SB_G0"),
+        abi::R_ARM_ALU_SB_G1_NC => Some("R_ARM_ALU_SB_G1_NC"),
+        abi::R_ARM_ALU_SB_G1 => Some("R_ARM_ALU_SB_G1"),
+        abi::R_ARM_ALU_SB_G2 => Some("R_ARM_ALU_SB_G2"),
+        abi::R_ARM_LDR_SB_G0 => Some("R_ARM_LDR_SB_G0"),
+        abi::R_ARM_LDR_SB_G1 => Some("R_ARM_LDR_SB_G1"),
+        abi::R_ARM_LDR_SB_G2 => Some("R_ARM_LDR_SB_G2"),
+        abi::R_ARM_LDRS_SB_G0 => Some("R_ARM_LDRS_SB_G0"),
+        abi::R_ARM_LDRS_SB_G1 => Some("R_ARM_LDRS_SB_G1"),
+        abi::R_ARM_LDRS_SB_G2 => Some("R_ARM_LDRS_SB_G2"),
+        abi::R_ARM_LDC_SB_G0 => Some("R_ARM_LDC_SB_G0"),
+        abi::R_ARM_LDC_SB_G1 => Some("R_ARM_LDC_SB_G1"),
+        abi::R_ARM_LDC_SB_G2 => Some("R_ARM_LDC_SB_G2"),
+        abi::R_ARM_MOVW_BREL_NC => Some("R_ARM_MOVW_BREL_NC"),
+        abi::R_ARM_MOVT_BREL => Some("R_ARM_MOVT_BREL"),
+        abi::R_ARM_MOVW_BREL => Some("R_ARM_MOVW_BREL"),
+        abi::R_ARM_THM_MOVW_BREL_NC => Some("R_ARM_THM_MOVW_BREL_NC"),
+        abi::R_ARM_THM_MOVT_BREL => Some("R_ARM_THM_MOVT_BREL"),
+        abi::R_ARM_THM_MOVW_BREL => Some("R_ARM_THM_MOVW_BREL"),
+        abi::R_ARM_TLS_GOTDESC => Some("R_ARM_TLS_GOTDESC"),
+        abi::R_ARM_TLS_CALL => Some("R_ARM_TLS_CALL"),
+        abi::R_ARM_TLS_DESCSEQ => Some("R_ARM_TLS_DESCSEQ"),
+        abi::R_ARM_THM_TLS_CALL => Some("R_ARM_THM_TLS_CALL"),
+        abi::R_ARM_PLT32_ABS => Some("R_ARM_PLT32_ABS"),
+        abi::R_ARM_GOT_ABS => Some("R_ARM_GOT_ABS"),
+        abi::R_ARM_GOT_PREL => Some("R_ARM_GOT_PREL"),
+        abi::R_ARM_GOT_BREL12 => Some("R_ARM_GOT_BREL12"),
+        abi::R_ARM_GOTOFF12 => Some("R_ARM_GOTOFF12"),
+        abi::R_ARM_GOTRELAX => Some("R_ARM_GOTRELAX"),
+        abi::R_ARM_GNU_VTENTRY => Some("R_ARM_GNU_VTENTRY"),
+        abi::R_ARM_GNU_VTINHERIT => Some("R_ARM_GNU_VTINHERIT"),
+        abi::R_ARM_THM_JUMP11 => Some("R_ARM_THM_JUMP11"),
+        abi::R_ARM_THM_JUMP8 => Some("R_ARM_THM_JUMP8"),
+        abi::R_ARM_TLS_GD32 => Some("R_ARM_TLS_GD32"),
+        abi::R_ARM_TLS_LDM32 => Some("R_ARM_TLS_LDM32"),
+        abi::R_ARM_TLS_LDO32 => Some("R_ARM_TLS_LDO32"),
+        abi::R_ARM_TLS_IE32 => Some("R_ARM_TLS_IE32"),
+        abi::R_ARM_TLS_LE32 => Some("R_ARM_TLS_LE32"),
+        abi::R_ARM_TLS_LDO12 => Some("R_ARM_TLS_LDO12"),
+        abi::R_ARM_TLS_LE12 => Some("R_ARM_TLS_LE12"),
+        abi::R_ARM_TLS_IE12GP => Some("R_ARM_TLS_IE12GP"),
+        abi::R_ARM_ME_TOO => Some("R_ARM_ME_TOO"),
+        abi::R_ARM_THM_TLS_DESCSEQ16 => Some("R_ARM_THM_TLS_DESCSEQ16"),
+        abi::R_ARM_THM_TLS_DESCSEQ32 => Some("R_ARM_THM_TLS_DESCSEQ32"),
+        abi::R_ARM_THM_GOT_BREL12 => Some("R_ARM_THM_GOT_BREL12"),
+        abi::R_ARM_THM_ALU_ABS_G0_NC => Some("R_ARM_THM_ALU_ABS_G0_NC"),
+        abi::R_ARM_THM_ALU_ABS_G1_NC => Some("R_ARM_THM_ALU_ABS_G1_NC"),
+        abi::R_ARM_THM_ALU_ABS_G2_NC => Some("R_ARM_THM_ALU_ABS_G2_NC"),
+        abi::R_ARM_THM_ALU_ABS_G3 => Some("R_ARM_THM_ALU_ABS_G3"),
+        abi::R_ARM_THM_BF16 => Some("R_ARM_THM_BF16"),
+        abi::R_ARM_THM_BF12 => Some("R_ARM_THM_BF12"),
+        abi::R_ARM_THM_BF18 => Some("R_ARM_THM_BF18"),
+        abi::R_ARM_IRELATIVE => Some("R_ARM_IRELATIVE"),
+        _ => None,
+    }
+}
+
+pub fn r_ppc_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_PPC_NONE => Some("R_PPC_NONE"),
+        abi::R_PPC_ADDR32 => Some("R_PPC_ADDR32"),
+        abi::R_PPC_ADDR24 => Some("R_PPC_ADDR24"),
+        abi::R_PPC_ADDR16 => Some("R_PPC_ADDR16"),
+        abi::R_PPC_ADDR16_LO => Some("R_PPC_ADDR16_LO"),
+        abi::R_PPC_ADDR16_HI => Some("R_PPC_ADDR16_HI"),
+        abi::R_PPC_ADDR16_HA => Some("R_PPC_ADDR16_HA"),
+        abi::R_PPC_ADDR14 => Some("R_PPC_ADDR14"),
+        abi::R_PPC_ADDR14_BRTAKEN => Some("R_PPC_ADDR14_BRTAKEN"),
+        abi::R_PPC_ADDR14_BRNTAKEN => Some("R_PPC_ADDR14_BRNTAKEN"),
+        abi::R_PPC_REL24 => Some("R_PPC_REL24"),
+        abi::R_PPC_REL14 => Some("R_PPC_REL14"),
+        abi::R_PPC_REL14_BRTAKEN => Some("R_PPC_REL14_BRTAKEN"),
+        abi::R_PPC_REL14_BRNTAKEN => Some("R_PPC_REL14_BRNTAKEN"),
+        abi::R_PPC_GOT16 => Some("R_PPC_GOT16"),
+        abi::R_PPC_GOT16_LO => Some("R_PPC_GOT16_LO"),
+        abi::R_PPC_GOT16_HI => Some("R_PPC_GOT16_HI"),
+        abi::R_PPC_GOT16_HA => Some("R_PPC_GOT16_HA"),
+        abi::R_PPC_PLTREL24 => Some("R_PPC_PLTREL24"),
+        abi::R_PPC_COPY => Some("R_PPC_COPY"),
+        abi::R_PPC_GLOB_DAT => Some("R_PPC_GLOB_DAT"),
+        abi::R_PPC_JMP_SLOT => Some("R_PPC_JMP_SLOT"),
+        abi::R_PPC_RELATIVE => Some("R_PPC_RELATIVE"),
+        abi::R_PPC_LOCAL24PC => Some("R_PPC_LOCAL24PC"),
+        abi::R_PPC_UADDR32 => Some("R_PPC_UADDR32"),
+        abi::R_PPC_UADDR16 => Some("R_PPC_UADDR16"),
+        abi::R_PPC_REL32 => Some("R_PPC_REL32"),
+        abi::R_PPC_PLT32 => Some("R_PPC_PLT32"),
+        abi::R_PPC_PLTREL32 => Some("R_PPC_PLTREL32"),
+        abi::R_PPC_PLT16_LO => Some("R_PPC_PLT16_LO"),
+        abi::R_PPC_PLT16_HI => Some("R_PPC_PLT16_HI"),
+        abi::R_PPC_PLT16_HA => Some("R_PPC_PLT16_HA"),
+        abi::R_PPC_SDAREL16 => Some("R_PPC_SDAREL16"),
+        abi::R_PPC_SECTOFF => Some("R_PPC_SECTOFF"),
+        abi::R_PPC_SECTOFF_LO => Some("R_PPC_SECTOFF_LO"),
+        abi::R_PPC_SECTOFF_HI => Some("R_PPC_SECTOFF_HI"),
+        abi::R_PPC_SECTOFF_HA => Some("R_PPC_SECTOFF_HA"),
+        abi::R_PPC_TLS => Some("R_PPC_TLS"),
+        abi::R_PPC_DTPMOD32 => Some("R_PPC_DTPMOD32"),
+        abi::R_PPC_TPREL16 => Some("R_PPC_TPREL16"),
+        abi::R_PPC_TPREL16_LO => Some("R_PPC_TPREL16_LO"),
+        abi::R_PPC_TPREL16_HI => Some("R_PPC_TPREL16_HI"),
+        abi::R_PPC_TPREL16_HA => Some("R_PPC_TPREL16_HA"),
+        abi::R_PPC_TPREL32 => Some("R_PPC_TPREL32"),
+        abi::R_PPC_DTPREL16 => Some("R_PPC_DTPREL16"),
+        abi::R_PPC_DTPREL16_LO => Some("R_PPC_DTPREL16_LO"),
+        abi::R_PPC_DTPREL16_HI => Some("R_PPC_DTPREL16_HI"),
+        abi::R_PPC_DTPREL16_HA => Some("R_PPC_DTPREL16_HA"),
+        abi::R_PPC_DTPREL32 => Some("R_PPC_DTPREL32"),
+        abi::R_PPC_GOT_TLSGD16 => Some("R_PPC_GOT_TLSGD16"),
+        abi::R_PPC_GOT_TLSGD16_LO => Some("R_PPC_GOT_TLSGD16_LO"),
+        abi::R_PPC_GOT_TLSGD16_HI => Some("R_PPC_GOT_TLSGD16_HI"),
+        abi::R_PPC_GOT_TLSGD16_HA => Some("R_PPC_GOT_TLSGD16_HA"),
+        abi::R_PPC_GOT_TLSLD16 => Some("R_PPC_GOT_TLSLD16"),
+        abi::R_PPC_GOT_TLSLD16_LO => Some("R_PPC_GOT_TLSLD16_LO"),
+        abi::R_PPC_GOT_TLSLD16_HI => Some("R_PPC_GOT_TLSLD16_HI"),
+        abi::R_PPC_GOT_TLSLD16_HA => Some("R_PPC_GOT_TLSLD16_HA"),
+        abi::R_PPC_GOT_TPREL16 => Some("R_PPC_GOT_TPREL16"),
+        abi::R_PPC_GOT_TPREL16_LO => Some("R_PPC_GOT_TPREL16_LO"),
+        abi::R_PPC_GOT_TPREL16_HI => Some("R_PPC_GOT_TPREL16_HI"),
+        abi::R_PPC_GOT_TPREL16_HA => Some("R_PPC_GOT_TPREL16_HA"),
+        abi::R_PPC_GOT_DTPREL16 => Some("R_PPC_GOT_DTPREL16"),
+        abi::R_PPC_GOT_DTPREL16_LO => Some("R_PPC_GOT_DTPREL16_LO"),
+        abi::R_PPC_GOT_DTPREL16_HI => Some("R_PPC_GOT_DTPREL16_HI"),
+        abi::R_PPC_GOT_DTPREL16_HA => Some("R_PPC_GOT_DTPREL16_HA"),
+        abi::R_PPC_TLSGD => Some("R_PPC_TLSGD"),
+        abi::R_PPC_TLSLD => Some("R_PPC_TLSLD"),
+        abi::R_PPC_EMB_NADDR32 => Some("R_PPC_EMB_NADDR32"),
+        abi::R_PPC_EMB_NADDR16 => Some("R_PPC_EMB_NADDR16"),
+        abi::R_PPC_EMB_NADDR16_LO => Some("R_PPC_EMB_NADDR16_LO"),
+        abi::R_PPC_EMB_NADDR16_HI => Some("R_PPC_EMB_NADDR16_HI"),
+        abi::R_PPC_EMB_NADDR16_HA => Some("R_PPC_EMB_NADDR16_HA"),
+        abi::R_PPC_EMB_SDAI16 => Some("R_PPC_EMB_SDAI16"),
+        abi::R_PPC_EMB_SDA2I16 => Some("R_PPC_EMB_SDA2I16"),
+        abi::R_PPC_EMB_SDA2REL => Some("R_PPC_EMB_SDA2REL"),
+        abi::R_PPC_EMB_SDA21 => Some("R_PPC_EMB_SDA21"),
+        abi::R_PPC_EMB_MRKREF => Some("R_PPC_EMB_MRKREF"),
+        abi::R_PPC_EMB_RELSEC16 => Some("R_PPC_EMB_RELSEC16"),
+        abi::R_PPC_EMB_RELST_LO => Some("R_PPC_EMB_RELST_LO"),
+        abi::R_PPC_EMB_RELST_HI => Some("R_PPC_EMB_RELST_HI"),
+        abi::R_PPC_EMB_RELST_HA => Some("R_PPC_EMB_RELST_HA"),
+        abi::R_PPC_EMB_BIT_FLD => Some("R_PPC_EMB_BIT_FLD"),
+        abi::R_PPC_EMB_RELSDA => Some("R_PPC_EMB_RELSDA"),
+        abi::R_PPC_DIAB_SDA21_LO => Some("R_PPC_DIAB_SDA21_LO"),
+        abi::R_PPC_DIAB_SDA21_HI => Some("R_PPC_DIAB_SDA21_HI"),
+        abi::R_PPC_DIAB_SDA21_HA => Some("R_PPC_DIAB_SDA21_HA"),
+        abi::R_PPC_DIAB_RELSDA_LO => Some("R_PPC_DIAB_RELSDA_LO"),
+        abi::R_PPC_DIAB_RELSDA_HI => Some("R_PPC_DIAB_RELSDA_HI"),
+        abi::R_PPC_DIAB_RELSDA_HA => Some("R_PPC_DIAB_RELSDA_HA"),
+        abi::R_PPC_IRELATIVE => Some("R_PPC_IRELATIVE"),
+        abi::R_PPC_REL16 => Some("R_PPC_REL16"),
+        abi::R_PPC_REL16_LO => Some("R_PPC_REL16_LO"),
+        abi::R_PPC_REL16_HI => Some("R_PPC_REL16_HI"),
+        abi::R_PPC_REL16_HA => Some("R_PPC_REL16_HA"),
+        abi::R_PPC_TOC16 => Some("R_PPC_TOC16"),
+        _ => None,
+    }
+}
+
+pub fn r_ppc64_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_PPC64_NONE => Some("R_PPC64_NONE"),
+        abi::R_PPC64_ADDR32 => Some("R_PPC64_ADDR32"),
+        abi::R_PPC64_ADDR24 => Some("R_PPC64_ADDR24"),
+        abi::R_PPC64_ADDR16 => Some("R_PPC64_ADDR16"),
+        abi::R_PPC64_ADDR16_LO => Some("R_PPC64_ADDR16_LO"),
+        abi::R_PPC64_ADDR16_HI => Some("R_PPC64_ADDR16_HI"),
+        abi::R_PPC64_ADDR16_HA => Some("R_PPC64_ADDR16_HA"),
+        abi::R_PPC64_ADDR14 => Some("R_PPC64_ADDR14"),
+        abi::R_PPC64_ADDR14_BRTAKEN => Some("R_PPC64_ADDR14_BRTAKEN"),
+        abi::R_PPC64_ADDR14_BRNTAKEN => Some("R_PPC64_ADDR14_BRNTAKEN"),
+        abi::R_PPC64_REL24 => Some("R_PPC64_REL24"),
+        abi::R_PPC64_REL14 => Some("R_PPC64_REL14"),
+        abi::R_PPC64_REL14_BRTAKEN => Some("R_PPC64_REL14_BRTAKEN"),
+        abi::R_PPC64_REL14_BRNTAKEN => Some("R_PPC64_REL14_BRNTAKEN"),
+        abi::R_PPC64_GOT16 => Some("R_PPC64_GOT16"),
+        abi::R_PPC64_GOT16_LO => Some("R_PPC64_GOT16_LO"),
+        abi::R_PPC64_GOT16_HI => Some("R_PPC64_GOT16_HI"),
+        abi::R_PPC64_GOT16_HA => Some("R_PPC64_GOT16_HA"),
+        abi::R_PPC64_COPY => Some("R_PPC64_COPY"),
+        abi::R_PPC64_GLOB_DAT => Some("R_PPC64_GLOB_DAT"),
+        abi::R_PPC64_JMP_SLOT => Some("R_PPC64_JMP_SLOT"),
+        abi::R_PPC64_RELATIVE => Some("R_PPC64_RELATIVE"),
+        abi::R_PPC64_UADDR32 => Some("R_PPC64_UADDR32"),
+        abi::R_PPC64_UADDR16 => Some("R_PPC64_UADDR16"),
+        abi::R_PPC64_REL32 => Some("R_PPC64_REL32"),
+        abi::R_PPC64_PLT32 => Some("R_PPC64_PLT32"),
+        abi::R_PPC64_PLTREL32 => Some("R_PPC64_PLTREL32"),
+        abi::R_PPC64_PLT16_LO => Some("R_PPC64_PLT16_LO"),
+        abi::R_PPC64_PLT16_HI => Some("R_PPC64_PLT16_HI"),
+        abi::R_PPC64_PLT16_HA => Some("R_PPC64_PLT16_HA"),
+        abi::R_PPC64_SECTOFF => Some("R_PPC64_SECTOFF"),
+        abi::R_PPC64_SECTOFF_LO => Some("R_PPC64_SECTOFF_LO"),
+        abi::R_PPC64_SECTOFF_HI => Some("R_PPC64_SECTOFF_HI"),
+        abi::R_PPC64_SECTOFF_HA => Some("R_PPC64_SECTOFF_HA"),
+        abi::R_PPC64_ADDR30 => Some("R_PPC64_ADDR30"),
+        abi::R_PPC64_ADDR64 => Some("R_PPC64_ADDR64"),
+        abi::R_PPC64_ADDR16_HIGHER => Some("R_PPC64_ADDR16_HIGHER"),
+        abi::R_PPC64_ADDR16_HIGHERA => Some("R_PPC64_ADDR16_HIGHERA"),
+        abi::R_PPC64_ADDR16_HIGHEST => Some("R_PPC64_ADDR16_HIGHEST"),
+        abi::R_PPC64_ADDR16_HIGHESTA => Some("R_PPC64_ADDR16_HIGHESTA"),
+        abi::R_PPC64_UADDR64 => Some("R_PPC64_UADDR64"),
+        abi::R_PPC64_REL64 => Some("R_PPC64_REL64"),
+        abi::R_PPC64_PLT64 => Some("R_PPC64_PLT64"),
+        abi::R_PPC64_PLTREL64 => Some("R_PPC64_PLTREL64"),
+        abi::R_PPC64_TOC16 => Some("R_PPC64_TOC16"),
+        abi::R_PPC64_TOC16_LO => Some("R_PPC64_TOC16_LO"),
+        abi::R_PPC64_TOC16_HI => Some("R_PPC64_TOC16_HI"),
+        abi::R_PPC64_TOC16_HA => Some("R_PPC64_TOC16_HA"),
+        abi::R_PPC64_TOC => Some("R_PPC64_TOC"),
+        abi::R_PPC64_PLTGOT16 => Some("R_PPC64_PLTGOT16"),
+        abi::R_PPC64_PLTGOT16_LO => Some("R_PPC64_PLTGOT16_LO"),
+        abi::R_PPC64_PLTGOT16_HI => Some("R_PPC64_PLTGOT16_HI"),
+        abi::R_PPC64_PLTGOT16_HA => Some("R_PPC64_PLTGOT16_HA"),
+        abi::R_PPC64_ADDR16_DS => Some("R_PPC64_ADDR16_DS"),
+        abi::R_PPC64_ADDR16_LO_DS => Some("R_PPC64_ADDR16_LO_DS"),
+        abi::R_PPC64_GOT16_DS => Some("R_PPC64_GOT16_DS"),
+        abi::R_PPC64_GOT16_LO_DS => Some("R_PPC64_GOT16_LO_DS"),
+        abi::R_PPC64_PLT16_LO_DS => Some("R_PPC64_PLT16_LO_DS"),
+        abi::R_PPC64_SECTOFF_DS => Some("R_PPC64_SECTOFF_DS"),
+        abi::R_PPC64_SECTOFF_LO_DS => Some("R_PPC64_SECTOFF_LO_DS"),
+        abi::R_PPC64_TOC16_DS => Some("R_PPC64_TOC16_DS"),
+        abi::R_PPC64_TOC16_LO_DS => Some("R_PPC64_TOC16_LO_DS"),
+        abi::R_PPC64_PLTGOT16_DS => Some("R_PPC64_PLTGOT16_DS"),
+        abi::R_PPC64_PLTGOT16_LO_DS => Some("R_PPC64_PLTGOT16_LO_DS"),
+        abi::R_PPC64_TLS => Some("R_PPC64_TLS"),
+        abi::R_PPC64_DTPMOD64 => Some("R_PPC64_DTPMOD64"),
+        abi::R_PPC64_TPREL16 => Some("R_PPC64_TPREL16"),
+        abi::R_PPC64_TPREL16_LO => Some("R_PPC64_TPREL16_LO"),
+        abi::R_PPC64_TPREL16_HI => Some("R_PPC64_TPREL16_HI"),
+        abi::R_PPC64_TPREL16_HA => Some("R_PPC64_TPREL16_HA"),
+        abi::R_PPC64_TPREL64 => Some("R_PPC64_TPREL64"),
+        abi::R_PPC64_DTPREL16 => Some("R_PPC64_DTPREL16"),
+        abi::R_PPC64_DTPREL16_LO => Some("R_PPC64_DTPREL16_LO"),
+        abi::R_PPC64_DTPREL16_HI => Some("R_PPC64_DTPREL16_HI"),
+        abi::R_PPC64_DTPREL16_HA => Some("R_PPC64_DTPREL16_HA"),
+        abi::R_PPC64_DTPREL64 => Some("R_PPC64_DTPREL64"),
+        abi::R_PPC64_GOT_TLSGD16 => Some("R_PPC64_GOT_TLSGD16"),
+        abi::R_PPC64_GOT_TLSGD16_LO => Some("R_PPC64_GOT_TLSGD16_LO"),
+        abi::R_PPC64_GOT_TLSGD16_HI => Some("R_PPC64_GOT_TLSGD16_HI"),
+        abi::R_PPC64_GOT_TLSGD16_HA => Some("R_PPC64_GOT_TLSGD16_HA"),
+        abi::R_PPC64_GOT_TLSLD16 => Some("R_PPC64_GOT_TLSLD16"),
+        abi::R_PPC64_GOT_TLSLD16_LO => Some("R_PPC64_GOT_TLSLD16_LO"),
+        abi::R_PPC64_GOT_TLSLD16_HI => Some("R_PPC64_GOT_TLSLD16_HI"),
+        abi::R_PPC64_GOT_TLSLD16_HA => Some("R_PPC64_GOT_TLSLD16_HA"),
+        abi::R_PPC64_GOT_TPREL16_DS => Some("R_PPC64_GOT_TPREL16_DS"),
+        abi::R_PPC64_GOT_TPREL16_LO_DS => Some("R_PPC64_GOT_TPREL16_LO_DS"),
+        abi::R_PPC64_GOT_TPREL16_HI => Some("R_PPC64_GOT_TPREL16_HI"),
+        abi::R_PPC64_GOT_TPREL16_HA => Some("R_PPC64_GOT_TPREL16_HA"),
+        abi::R_PPC64_GOT_DTPREL16_DS => Some("R_PPC64_GOT_DTPREL16_DS"),
+        abi::R_PPC64_GOT_DTPREL16_LO_DS => Some("R_PPC64_GOT_DTPREL16_LO_DS"),
+        abi::R_PPC64_GOT_DTPREL16_HI => Some("R_PPC64_GOT_DTPREL16_HI"),
+        abi::R_PPC64_GOT_DTPREL16_HA => Some("R_PPC64_GOT_DTPREL16_HA"),
+        abi::R_PPC64_TPREL16_DS => Some("R_PPC64_TPREL16_DS"),
+        abi::R_PPC64_TPREL16_LO_DS => Some("R_PPC64_TPREL16_LO_DS"),
+        abi::R_PPC64_TPREL16_HIGHER => Some("R_PPC64_TPREL16_HIGHER"),
+        abi::R_PPC64_TPREL16_HIGHERA => Some("R_PPC64_TPREL16_HIGHERA"),
+        abi::R_PPC64_TPREL16_HIGHEST => Some("R_PPC64_TPREL16_HIGHEST"),
+        abi::R_PPC64_TPREL16_HIGHESTA => Some("R_PPC64_TPREL16_HIGHESTA"),
+        abi::R_PPC64_DTPREL16_DS => Some("R_PPC64_DTPREL16_DS"),
+        abi::R_PPC64_DTPREL16_LO_DS => Some("R_PPC64_DTPREL16_LO_DS"),
+        abi::R_PPC64_DTPREL16_HIGHER => Some("R_PPC64_DTPREL16_HIGHER"),
+        abi::R_PPC64_DTPREL16_HIGHERA => Some("R_PPC64_DTPREL16_HIGHERA"),
+        abi::R_PPC64_DTPREL16_HIGHEST => Some("R_PPC64_DTPREL16_HIGHEST"),
+        abi::R_PPC64_DTPREL16_HIGHESTA => Some("R_PPC64_DTPREL16_HIGHESTA"),
+        abi::R_PPC64_TLSGD => Some("R_PPC64_TLSGD"),
+        abi::R_PPC64_TLSLD => Some("R_PPC64_TLSLD"),
+        abi::R_PPC64_TOCSAVE => Some("R_PPC64_TOCSAVE"),
+        abi::R_PPC64_ADDR16_HIGH => Some("R_PPC64_ADDR16_HIGH"),
+        abi::R_PPC64_ADDR16_HIGHA => Some("R_PPC64_ADDR16_HIGHA"),
+        abi::R_PPC64_TPREL16_HIGH => Some("R_PPC64_TPREL16_HIGH"),
+        abi::R_PPC64_TPREL16_HIGHA => Some("R_PPC64_TPREL16_HIGHA"),
+        abi::R_PPC64_DTPREL16_HIGH => Some("R_PPC64_DTPREL16_HIGH"),
+        abi::R_PPC64_DTPREL16_HIGHA => Some("R_PPC64_DTPREL16_HIGHA"),
+        abi::R_PPC64_JMP_IREL => Some("R_PPC64_JMP_IREL"),
+        abi::R_PPC64_IRELATIVE => Some("R_PPC64_IRELATIVE"),
+        abi::R_PPC64_REL16 => Some("R_PPC64_REL16"),
+        abi::R_PPC64_REL16_LO => Some("R_PPC64_REL16_LO"),
+        abi::R_PPC64_REL16_HI => Some("R_PPC64_REL16_HI"),
+        abi::R_PPC64_REL16_HA => Some("R_PPC64_REL16_HA"),
+        _ => None,
+    }
+}
+
+pub fn r_riscv_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_RISCV_NONE => Some("R_RISCV_NONE"),
+        abi::R_RISCV_32 => Some("R_RISCV_32"),
+        abi::R_RISCV_64 => Some("R_RISCV_64"),
+        abi::R_RISCV_RELATIVE => Some("R_RISCV_RELATIVE"),
+        abi::R_RISCV_COPY => Some("R_RISCV_COPY"),
+        abi::R_RISCV_JUMP_SLOT => Some("R_RISCV_JUMP_SLOT"),
+        abi::R_RISCV_TLS_DTPMOD32 => Some("R_RISCV_TLS_DTPMOD32"),
+        abi::R_RISCV_TLS_DTPMOD64 => Some("R_RISCV_TLS_DTPMOD64"),
+        abi::R_RISCV_TLS_DTPREL32 => Some("R_RISCV_TLS_DTPREL32"),
+        abi::R_RISCV_TLS_DTPREL64 => Some("R_RISCV_TLS_DTPREL64"),
+        abi::R_RISCV_TLS_TPREL32 => Some("R_RISCV_TLS_TPREL32"),
+        abi::R_RISCV_TLS_TPREL64 => Some("R_RISCV_TLS_TPREL64"),
+        abi::R_RISCV_BRANCH => Some("R_RISCV_BRANCH"),
+        abi::R_RISCV_JAL => Some("R_RISCV_JAL"),
+        abi::R_RISCV_CALL => Some("R_RISCV_CALL"),
+        abi::R_RISCV_CALL_PLT => Some("R_RISCV_CALL_PLT"),
+        abi::R_RISCV_GOT_HI20 => Some("R_RISCV_GOT_HI20"),
+        abi::R_RISCV_TLS_GOT_HI20 => Some("R_RISCV_TLS_GOT_HI20"),
+        abi::R_RISCV_TLS_GD_HI20 => Some("R_RISCV_TLS_GD_HI20"),
+        abi::R_RISCV_PCREL_HI20 => Some("R_RISCV_PCREL_HI20"),
+        abi::R_RISCV_PCREL_LO12_I => Some("R_RISCV_PCREL_LO12_I"),
+        abi::R_RISCV_PCREL_LO12_S => Some("R_RISCV_PCREL_LO12_S"),
+        abi::R_RISCV_HI20 => Some("R_RISCV_HI20"),
+        abi::R_RISCV_LO12_I => Some("R_RISCV_LO12_I"),
+        abi::R_RISCV_LO12_S => Some("R_RISCV_LO12_S"),
+        abi::R_RISCV_TPREL_HI20 => Some("R_RISCV_TPREL_HI20"),
+        abi::R_RISCV_TPREL_LO12_I => Some("R_RISCV_TPREL_LO12_I"),
+        abi::R_RISCV_TPREL_LO12_S => Some("R_RISCV_TPREL_LO12_S"),
+        abi::R_RISCV_TPREL_ADD => Some("R_RISCV_TPREL_ADD"),
+        abi::R_RISCV_ADD8 => Some("R_RISCV_ADD8"),
+        abi::R_RISCV_ADD16 => Some("R_RISCV_ADD16"),
+        abi::R_RISCV_ADD32 => Some("R_RISCV_ADD32"),
+        abi::R_RISCV_ADD64 => Some("R_RISCV_ADD64"),
+        abi::R_RISCV_SUB8 => Some("R_RISCV_SUB8"),
+        abi::R_RISCV_SUB16 => Some("R_RISCV_SUB16"),
+        abi::R_RISCV_SUB32 => Some("R_RISCV_SUB32"),
+        abi::R_RISCV_SUB64 => Some("R_RISCV_SUB64"),
+        abi::R_RISCV_ALIGN => Some("R_RISCV_ALIGN"),
+        abi::R_RISCV_RVC_BRANCH => Some("R_RISCV_RVC_BRANCH"),
+        abi::R_RISCV_RVC_JUMP => Some("R_RISCV_RVC_JUMP"),
+        abi::R_RISCV_RVC_LUI => Some("R_RISCV_RVC_LUI"),
+        abi::R_RISCV_RELAX => Some("R_RISCV_RELAX"),
+        abi::R_RISCV_SUB6 => Some("R_RISCV_SUB6"),
+        abi::R_RISCV_SET6 => Some("R_RISCV_SET6"),
+        abi::R_RISCV_SET8 => Some("R_RISCV_SET8"),
+        abi::R_RISCV_SET16 => Some("R_RISCV_SET16"),
+        abi::R_RISCV_SET32 => Some("R_RISCV_SET32"),
+        abi::R_RISCV_32_PCREL => Some("R_RISCV_32_PCREL"),
+        abi::R_RISCV_IRELATIVE => Some("R_RISCV_IRELATIVE"),
+        _ => None,
+    }
+}
+
+pub fn r_386_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_386_NONE => Some("R_386_NONE"),
+        abi::R_386_32 => Some("R_386_32"),
+        abi::R_386_PC32 => Some("R_386_PC32"),
+        abi::R_386_GOT32 => Some("R_386_GOT32"),
+        abi::R_386_PLT32 => Some("R_386_PLT32"),
+        abi::R_386_COPY => Some("R_386_COPY"),
+        abi::R_386_GLOB_DAT => Some("R_386_GLOB_DAT"),
+        abi::R_386_JUMP_SLOT => Some("R_386_JUMP_SLOT"),
+        abi::R_386_RELATIVE => Some("R_386_RELATIVE"),
+        abi::R_386_GOTOFF => Some("R_386_GOTOFF"),
+        abi::R_386_GOTPC => Some("R_386_GOTPC"),
+        abi::R_386_32PLT => Some("R_386_32PLT"),
+        abi::R_386_TLS_TPOFF => Some("R_386_TLS_TPOFF"),
+        abi::R_386_TLS_IE => Some("R_386_TLS_IE"),
+        abi::R_386_TLS_GOTIE => Some("R_386_TLS_GOTIE"),
+        abi::R_386_TLS_LE => Some("R_386_TLS_LE"),
+        abi::R_386_TLS_GD => Some("R_386_TLS_GD"),
+        abi::R_386_TLS_LDM => Some("R_386_TLS_LDM"),
+        abi::R_386_16 => Some("R_386_16"),
+        abi::R_386_PC16 => Some("R_386_PC16"),
+        abi::R_386_8 => Some("R_386_8"),
+        abi::R_386_PC8 => Some("R_386_PC8"),
+        abi::R_386_TLS_GD_32 => Some("R_386_TLS_GD_32"),
+        abi::R_386_TLS_GD_PUSH => Some("R_386_TLS_GD_PUSH"),
+        abi::R_386_TLS_GD_CALL => Some("R_386_TLS_GD_CALL"),
+        abi::R_386_TLS_GD_POP => Some("R_386_TLS_GD_POP"),
+        abi::R_386_TLS_LDM_32 => Some("R_386_TLS_LDM_32"),
+        abi::R_386_TLS_LDM_PUSH => Some("R_386_TLS_LDM_PUSH"),
+        abi::R_386_TLS_LDM_CALL => Some("R_386_TLS_LDM_CALL"),
+        abi::R_386_TLS_LDM_POP => Some("R_386_TLS_LDM_POP"),
+        abi::R_386_TLS_LDO_32 => Some("R_386_TLS_LDO_32"),
+        abi::R_386_TLS_IE_32 => Some("R_386_TLS_IE_32"),
+        abi::R_386_TLS_LE_32 => Some("R_386_TLS_LE_32"),
+        abi::R_386_TLS_DTPMOD32 => Some("R_386_TLS_DTPMOD32"),
+        abi::R_386_TLS_DTPOFF32 => Some("R_386_TLS_DTPOFF32"),
+        abi::R_386_TLS_TPOFF32 => Some("R_386_TLS_TPOFF32"),
+        abi::R_386_SIZE32 => Some("R_386_SIZE32"),
+        abi::R_386_TLS_GOTDESC => Some("R_386_TLS_GOTDESC"),
+        abi::R_386_TLS_DESC_CALL => Some("R_386_TLS_DESC_CALL"),
+        abi::R_386_TLS_DESC => Some("R_386_TLS_DESC"),
+        abi::R_386_IRELATIVE => Some("R_386_IRELATIVE"),
+        abi::R_386_GOT32X => Some("R_386_GOT32X"),
+        abi::R_386_GNU_VTINHERIT => Some("R_386_GNU_VTINHERIT"),
+        abi::R_386_GNU_VTENTRY => Some("R_386_GNU_VTENTRY"),
+        _ => None,
+    }
+}
+
+pub fn r_x86_64_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_X86_64_NONE => Some("R_X86_64_NONE"),
+        abi::R_X86_64_64 => Some("R_X86_64_64"),
+        abi::R_X86_64_PC32 => Some("R_X86_64_PC32"),
+        abi::R_X86_64_GOT32 => Some("R_X86_64_GOT32"),
+        abi::R_X86_64_PLT32 => Some("R_X86_64_PLT32"),
+        abi::R_X86_64_COPY => Some("R_X86_64_COPY"),
+        abi::R_X86_64_GLOB_DAT => Some("R_X86_64_GLOB_DAT"),
+        abi::R_X86_64_JUMP_SLOT => Some("R_X86_64_JUMP_SLOT"),
+        abi::R_X86_64_RELATIVE => Some("R_X86_64_RELATIVE"),
+        abi::R_X86_64_GOTPCREL => Some("R_X86_64_GOTPCREL"),
+        abi::R_X86_64_32 => Some("R_X86_64_32"),
+        abi::R_X86_64_32S => Some("R_X86_64_32S"),
+        abi::R_X86_64_16 => Some("R_X86_64_16"),
+        abi::R_X86_64_PC16 => Some("R_X86_64_PC16"),
+        abi::R_X86_64_8 => Some("R_X86_64_8"),
+        abi::R_X86_64_PC8 => Some("R_X86_64_PC8"),
+        abi::R_X86_64_DTPMOD64 => Some("R_X86_64_DTPMOD64"),
+        abi::R_X86_64_DTPOFF64 => Some("R_X86_64_DTPOFF64"),
+        abi::R_X86_64_TPOFF64 => Some("R_X86_64_TPOFF64"),
+        abi::R_X86_64_TLSGD => Some("R_X86_64_TLSGD"),
+        abi::R_X86_64_TLSLD => Some("R_X86_64_TLSLD"),
+        abi::R_X86_64_DTPOFF32 => Some("R_X86_64_DTPOFF32"),
+        abi::R_X86_64_GOTTPOFF => Some("R_X86_64_GOTTPOFF"),
+        abi::R_X86_64_TPOFF32 => Some("R_X86_64_TPOFF32"),
+        abi::R_X86_64_PC64 => Some("R_X86_64_PC64"),
+        abi::R_X86_64_GOTOFF64 => Some("R_X86_64_GOTOFF64"),
+        abi::R_X86_64_GOTPC32 => Some("R_X86_64_GOTPC32"),
+        abi::R_X86_64_GOT64 => Some("R_X86_64_GOT64"),
+        abi::R_X86_64_GOTPCREL64 => Some("R_X86_64_GOTPCREL64"),
+        abi::R_X86_64_GOTPC64 => Some("R_X86_64_GOTPC64"),
+        abi::R_X86_64_PLTOFF64 => Some("R_X86_64_PLTOFF64"),
+        abi::R_X86_64_SIZE32 => Some("R_X86_64_SIZE32"),
+        abi::R_X86_64_SIZE64 => Some("R_X86_64_SIZE64"),
+        abi::R_X86_64_GOTPC32_TLSDESC => Some("R_X86_64_GOTPC32_TLSDESC"),
+        abi::R_X86_64_TLSDESC_CALL => Some("R_X86_64_TLSDESC_CALL"),
+        abi::R_X86_64_TLSDESC => Some("R_X86_64_TLSDESC"),
+        abi::R_X86_64_IRELATIVE => Some("R_X86_64_IRELATIVE"),
+        abi::R_X86_64_RELATIVE64 => Some("R_X86_64_RELATIVE64"),
+        abi::R_X86_64_GOTPCRELX => Some("R_X86_64_GOTPCRELX"),
+        abi::R_X86_64_REX_GOTPCRELX => Some("R_X86_64_REX_GOTPCRELX"),
+        _ => None,
+    }
+}
+
+/// Relocation type names depend on the machine, so this picks the table for `e_machine`
+pub fn r_type_to_str(e_machine: u16, r_type: u32) -> Option<&'static str> {
+    match e_machine {
+        abi::EM_386 | abi::EM_IAMCU => r_386_to_str(r_type),
+        abi::EM_AARCH64 => r_aarch64_to_str(r_type),
+        abi::EM_ARM => r_arm_to_str(r_type),
+        abi::EM_PPC => r_ppc_to_str(r_type),
+        abi::EM_PPC64 => r_ppc64_to_str(r_type),
+        abi::EM_RISCV => r_riscv_to_str(r_type),
+        abi::EM_X86_64 => r_x86_64_to_str(r_type),
+        _ => None,
+    }
+}
+
+pub fn r_type_to_string(e_machine: u16, r_type: u32) -> String {
+    match r_type_to_str(e_machine, r_type) {
+        Some(s) => s.to_string(),
+        None => format!("r_type({r_type:#x})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_flags() {
+        let flags = |flags: &[u32]| flags.iter().fold(0, |all, &flag| all | flag) as u64;
+        assert_eq!(sh_flags_to_string(0), "");
+        assert_eq!(
+            sh_flags_to_string(flags(&[abi::SHF_WRITE, abi::SHF_ALLOC, abi::SHF_EXECINSTR])),
+            "WAX"
+        );
+        assert_eq!(
+            sh_flags_to_string(flags(&[abi::SHF_MERGE, abi::SHF_STRINGS])),
+            "MS"
+        );
+        assert_eq!(sh_flags_to_string(flags(&[abi::SHF_INFO_LINK])), "I");
+        assert_eq!(sh_flags_to_string(flags(&[abi::SHF_LINK_ORDER])), "L");
+        assert_eq!(sh_flags_to_string(flags(&[abi::SHF_GROUP])), "G");
+        assert_eq!(
+            sh_flags_to_string(flags(&[abi::SHF_ALLOC, abi::SHF_WRITE, abi::SHF_TLS])),
+            "WAT"
+        );
+        assert_eq!(sh_flags_to_string(flags(&[abi::SHF_COMPRESSED])), "C");
+        assert_eq!(sh_flags_to_string(flags(&[abi::SHF_EXCLUDE])), "E");
+    }
+
+    #[test]
+    fn leftover_section_flags() {
+        assert_eq!(sh_flags_to_string(1 << 3), "x"); // Between SHF_EXECINSTR and SHF_MERGE
+        assert_eq!(sh_flags_to_string(1 << 12), "x");
+        assert_eq!(sh_flags_to_string(0x100000), "o");
+        assert_eq!(sh_flags_to_string(0x10000000), "p");
+        // Bits past the 32 an ELF32 word holds count as unknown
+        assert_eq!(sh_flags_to_string(1 << 32), "x");
+        let all = (abi::SHF_ALLOC | abi::SHF_EXCLUDE | 0x10100008) as u64;
+        assert_eq!(sh_flags_to_string(all), "AExop");
+    }
+
+    #[test]
+    fn dynamic_flags() {
+        assert_eq!(d_flags_to_string(0), "");
+        assert_eq!(
+            d_flags_to_string((abi::DF_ORIGIN | abi::DF_BIND_NOW) as u64),
+            "ORIGIN BIND_NOW"
+        );
+        assert_eq!(
+            d_flags_to_string((abi::DF_TEXTREL | abi::DF_STATIC_TLS | 0x100) as u64),
+            "TEXTREL STATIC_TLS 0x100"
+        );
+        assert_eq!(
+            d_flags_1_to_string((abi::DF_1_NOW | abi::DF_1_PIE) as u64),
+            "NOW PIE"
+        );
+        assert_eq!(
+            d_flags_1_to_string((abi::DF_1_NOCOMMON as u64) | 0x80000000 | (1 << 40)),
+            "NOCOMMON 0x10080000000"
+        );
+    }
+
+    #[test]
+    fn i386_relocation_types() {
+        assert_eq!(r_386_to_str(0), Some("R_386_NONE"));
+        assert_eq!(r_386_to_str(43), Some("R_386_GOT32X"));
+        assert_eq!(r_386_to_str(250), Some("R_386_GNU_VTINHERIT"));
+        assert_eq!(r_386_to_str(251), Some("R_386_GNU_VTENTRY"));
+        assert_eq!(r_386_to_str(44), None); // Between R_386_GOT32X and the vtable pair
+        assert_eq!(r_type_to_string(abi::EM_386, 44), "r_type(0x2c)");
+    }
+
+    #[test]
+    fn iamcu_relocation_types() {
+        for r_type in [
+            abi::R_386_NONE,
+            abi::R_386_GOT32X,
+            abi::R_386_GNU_VTENTRY,
+            44,
+        ] {
+            assert_eq!(
+                r_type_to_str(abi::EM_IAMCU, r_type),
+                r_type_to_str(abi::EM_386, r_type)
+            );
+        }
+        assert_eq!(r_type_to_string(abi::EM_IAMCU, 1), "R_386_32");
+        // Not the x86-64 name that shares the number
+        assert_ne!(
+            r_type_to_string(abi::EM_IAMCU, 1),
+            r_type_to_string(abi::EM_X86_64, 1)
+        );
+    }
+
+    #[test]
+    fn arm_flags() {
+        let eabi5 = abi::EF_ARM_EABI_VER5;
+        assert_eq!(
+            e_flags_to_string(abi::EM_ARM, eabi5 | abi::EF_ARM_ABI_FLOAT_HARD),
+            ", Version5 EABI, hard-float ABI"
+        );
+        assert_eq!(
+            e_flags_to_string(
+                abi::EM_ARM,
+                eabi5 | abi::EF_ARM_BE8 | abi::EF_ARM_ABI_FLOAT_SOFT
+            ),
+            ", Version5 EABI, BE8, soft-float ABI"
+        );
+        assert_eq!(
+            e_flags_to_string(abi::EM_ARM, abi::EF_ARM_PIC | abi::EF_ARM_SOFT_FLOAT),
+            ", position independent, GNU EABI, software FP"
+        );
+        // Bits the EABI version doesn't define
+        assert_eq!(
+            e_flags_to_string(abi::EM_ARM, abi::EF_ARM_EABI_VER4 | 0x4),
+            ", Version4 EABI, <unknown>"
+        );
+        assert_eq!(
+            e_flags_to_string(
+                abi::EM_ARM,
+                abi::EF_ARM_EABI_VER2 | abi::EF_ARM_ABI_FLOAT_HARD
+            ),
+            ", Version2 EABI, <unknown>"
+        );
+        assert_eq!(
+            e_flags_to_string(abi::EM_ARM, 0x06000000),
+            ", <unrecognized EABI>"
+        );
+    }
+
+    #[test]
+    fn power_pc_flags() {
+        assert_eq!(
+            e_flags_to_string(abi::EM_PPC, abi::EF_PPC_EMB | abi::EF_PPC_RELOCATABLE_LIB),
+            ", emb, relocatable-lib"
+        );
+        // readelf leaves other bits out, rather than marking them unknown
+        assert_eq!(
+            e_flags_to_string(abi::EM_PPC, abi::EF_PPC_RELOCATABLE | 0x1),
+            ", relocatable"
+        );
+        assert_eq!(e_flags_to_string(abi::EM_PPC64, 2), ", abiv2");
+        assert_eq!(e_flags_to_string(abi::EM_PPC64, 0x100), "");
+    }
+
+    #[test]
+    fn riscv_flags() {
+        assert_eq!(
+            e_flags_to_string(
+                abi::EM_RISCV,
+                abi::EF_RISCV_RVC | abi::EF_RISCV_FLOAT_ABI_DOUBLE
+            ),
+            ", RVC, double-float ABI"
+        );
+        assert_eq!(
+            e_flags_to_string(abi::EM_RISCV, abi::EF_RISCV_RVE | abi::EF_RISCV_TSO),
+            ", RVE, TSO, soft-float ABI"
+        );
+        assert_eq!(
+            e_flags_to_string(abi::EM_RISCV, abi::EF_RISCV_FLOAT_ABI_QUAD | 0x100),
+            ", quad-float ABI"
+        );
+        assert_eq!(e_flags_to_string(abi::EM_RISCV, 0), "");
+        // Nothing is decoded for machines without a table
+        assert_eq!(e_flags_to_string(abi::EM_X86_64, 0x1), "");
+    }
+}
@@ -0,0 +1,79 @@
+//! Replays the fuzz corpus, so inputs that once crashed the parser stay fixed. Each input
+//! may fail to parse, but must neither panic in the library nor in any dump of the binary.
+
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::process::Command;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/parse_elf");
+
+fn corpus() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(CORPUS)
+        .expect("fuzz corpus is missing")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn corpus_parses_without_panicking() {
+    for path in corpus() {
+        let buffer = fs::read(&path).unwrap();
+        let result = panic::catch_unwind(|| mark_readelf::fuzz::exercise(&buffer));
+        assert!(result.is_ok(), "{} panicked", path.display());
+    }
+}
+
+#[test]
+fn corpus_dumps_without_panicking() {
+    for path in corpus() {
+        for json in [false, true] {
+            let mut command = Command::new(env!("CARGO_BIN_EXE_mark-readelf"));
+            command.args(["-a", "--lookup=main", "--required-versions", "--lenient"]);
+            if json {
+                command.arg("--json");
+            }
+            let output = command.arg(&path).output().unwrap();
+            // Rust exits with 101 on a panic, where a parse error exits with 1
+            assert_ne!(
+                output.status.code(),
+                Some(101),
+                "{} panicked:\n{}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+}
+
+/// Inputs with one table that can't be read, and the section that table is in. Only that
+/// table is left out, so the rest of the file still dumps, with a warning about it.
+const ONE_BAD_TABLE: [(&str, &str); 4] = [
+    ("dynamic-segment-past-end", ".text"),
+    ("gnu-version-past-end", ".gnu.version"),
+    ("gnu-hash-short", ".gnu.hash"),
+    ("hash-short", ".hash"),
+];
+
+#[test]
+fn one_bad_table_leaves_the_rest() {
+    for (file, section) in ONE_BAD_TABLE {
+        let output = Command::new(env!("CARGO_BIN_EXE_mark-readelf"))
+            .args(["-a", &format!("{CORPUS}/{file}")])
+            .output()
+            .unwrap();
+        let (stdout, stderr) = (
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        );
+        assert!(output.status.success(), "{file} failed:\n{stderr}");
+        assert!(stderr.contains("Warning"), "{file} has no warning");
+        assert!(stdout.contains("Section Headers:"), "{file}:\n{stdout}");
+        assert!(
+            stdout.contains(&format!("] {section} ")),
+            "{file}:\n{stdout}"
+        );
+    }
+}